@@ -0,0 +1,175 @@
+// Long-text chunking and audio concatenation for the Deepgram `/speak` endpoint,
+// which rejects requests whose text exceeds a per-request character limit.
+use crate::client::{DeepgramTtsApi, TextToSpeechParams, TextToSpeechRequest, TtsResponse};
+use golem_tts::golem::tts::types::TtsError;
+
+/// Default fragment size, equal to Deepgram's 2000-character per-request limit.
+pub const DEFAULT_MAX_CHARS: usize = 2000;
+
+/// Encodings whose bytes can be concatenated directly because they carry no
+/// container framing (as opposed to e.g. WAV, which has a header describing
+/// the total data length).
+pub(crate) fn is_concatenatable_encoding(encoding: &str) -> bool {
+    matches!(encoding, "linear16" | "mp3" | "mulaw" | "alaw" | "opus")
+}
+
+/// Split `text` into fragments no larger than `max_chars`, breaking on
+/// whitespace where possible so words are never split across a boundary.
+///
+/// Whitespace is first canonicalized (trimmed, runs collapsed to a single
+/// space) so fragment boundaries are deterministic regardless of the
+/// caller's formatting.
+pub fn split_text_into_fragments(text: &str, max_chars: usize) -> Vec<String> {
+    let canonical = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if canonical.is_empty() {
+        return vec![];
+    }
+
+    if canonical.chars().count() <= max_chars {
+        return vec![canonical];
+    }
+
+    let mut fragments = Vec::new();
+    let mut remaining: Vec<char> = canonical.chars().collect();
+
+    while remaining.len() > max_chars {
+        let window: Vec<char> = remaining[..max_chars + 1].to_vec();
+        let break_at = window
+            .iter()
+            .rposition(|c| *c == ' ')
+            .unwrap_or(max_chars);
+
+        let fragment: String = window[..break_at].iter().collect();
+        fragments.push(fragment);
+
+        // Skip the separating space (if any) before continuing.
+        let skip = if break_at < window.len() && window[break_at] == ' ' {
+            break_at + 1
+        } else {
+            break_at
+        };
+        remaining = remaining[skip..].to_vec();
+    }
+
+    if !remaining.is_empty() {
+        fragments.push(remaining.into_iter().collect());
+    }
+
+    fragments
+}
+
+/// Returns an error if concatenating `fragment_count` fragments of
+/// `encoding` audio would corrupt the result, unless `allow_rewrap` opts
+/// in anyway. A single fragment never needs concatenation, so it's always
+/// fine; multiple fragments are only a problem for encodings outside
+/// `is_concatenatable_encoding`. Any WAV container framing is applied once,
+/// after concatenation, by the caller (see `audio_data_to_synthesis_result`),
+/// so a `container` of `"wav"` never factors in here.
+pub(crate) fn concatenation_error(
+    fragment_count: usize,
+    encoding: &str,
+    allow_rewrap: bool,
+) -> Option<TtsError> {
+    if fragment_count > 1 && !is_concatenatable_encoding(encoding) && !allow_rewrap {
+        Some(TtsError::UnsupportedOperation(format!(
+            "Cannot concatenate {} chunked fragments for encoding {:?}; \
+             request a raw encoding (e.g. linear16, mp3) or opt into re-wrapping",
+            fragment_count, encoding
+        )))
+    } else {
+        None
+    }
+}
+
+/// Synthesize `text` as one or more fragments of at most `max_chars`
+/// characters each, joining the resulting audio into a single response.
+pub fn text_to_speech_chunked(
+    client: &DeepgramTtsApi,
+    text: &str,
+    params: Option<&TextToSpeechParams>,
+    max_chars: usize,
+    allow_rewrap: bool,
+) -> Result<TtsResponse, TtsError> {
+    let encoding = params
+        .and_then(|p| p.encoding.clone())
+        .unwrap_or_else(|| "linear16".to_string());
+
+    let fragments = split_text_into_fragments(text, max_chars);
+    if fragments.is_empty() {
+        return Err(TtsError::InvalidText("Text cannot be empty".to_string()));
+    }
+
+    if let Some(err) = concatenation_error(fragments.len(), &encoding, allow_rewrap) {
+        return Err(err);
+    }
+
+    let mut merged_audio = Vec::new();
+    let mut merged = None;
+
+    for fragment in fragments {
+        let request = TextToSpeechRequest { text: fragment };
+        let response = client.text_to_speech_with_metadata(&request, params)?;
+        merged_audio.extend_from_slice(&response.audio_data);
+
+        merged = Some(match merged {
+            None => response.metadata,
+            Some(mut acc) => {
+                acc.dg_char_count += response.metadata.dg_char_count;
+                acc
+            }
+        });
+    }
+
+    Ok(TtsResponse {
+        audio_data: merged_audio,
+        metadata: merged.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_a_single_fragment() {
+        let fragments = split_text_into_fragments("hello   world", 2000);
+        assert_eq!(fragments, vec!["hello world".to_string()]);
+    }
+
+    #[test]
+    fn long_text_breaks_on_whitespace() {
+        let text = format!("{} {}", "a".repeat(10), "b".repeat(10));
+        let fragments = split_text_into_fragments(&text, 10);
+        assert_eq!(fragments, vec!["a".repeat(10), "b".repeat(10)]);
+    }
+
+    #[test]
+    fn falls_back_to_hard_cut_without_whitespace() {
+        let text = "a".repeat(25);
+        let fragments = split_text_into_fragments(&text, 10);
+        assert_eq!(fragments, vec!["a".repeat(10), "a".repeat(10), "a".repeat(5)]);
+    }
+
+    #[test]
+    fn single_fragment_never_errors_regardless_of_encoding() {
+        // This is the default `synthesize` path: no audio_config, so
+        // encoding defaults to "linear16" with a "wav" container, and
+        // short text never splits into more than one fragment.
+        assert!(concatenation_error(1, "linear16", false).is_none());
+        assert!(concatenation_error(1, "some-future-codec", false).is_none());
+    }
+
+    #[test]
+    fn multiple_fragments_of_a_concatenatable_encoding_never_error() {
+        for encoding in ["linear16", "mp3", "mulaw", "alaw", "opus"] {
+            assert!(concatenation_error(3, encoding, false).is_none());
+        }
+    }
+
+    #[test]
+    fn multiple_fragments_of_a_non_concatenatable_encoding_error_unless_allowed() {
+        assert!(concatenation_error(2, "some-future-codec", false).is_some());
+        assert!(concatenation_error(2, "some-future-codec", true).is_none());
+    }
+}
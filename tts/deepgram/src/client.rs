@@ -7,14 +7,27 @@ use golem_tts::golem::tts::types::TtsError;
 use log::trace;
 use reqwest::{Client, Method, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::mpsc;
 use std::time::Duration;
 
+/// An incremental event from `text_to_speech_stream`: the header-derived
+/// metadata arrives first, followed by zero or more raw audio frames.
+#[derive(Debug)]
+pub enum StreamEvent {
+    Metadata(TtsResponseMetadata),
+    Chunk(Vec<u8>),
+}
+
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub max_retries: u32,
     pub initial_delay: Duration,
     pub max_delay: Duration,
     pub backoff_multiplier: f64,
+    /// Apply full-jitter (sleep a random duration in `[0, computed_delay]`)
+    /// so concurrent clients desynchronize instead of retrying in lockstep.
+    pub jitter: bool,
 }
 
 impl Default for RateLimitConfig {
@@ -24,10 +37,32 @@ impl Default for RateLimitConfig {
             initial_delay: Duration::from_millis(1000),
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
+            jitter: true,
         }
     }
 }
 
+/// Parse a `Retry-After` header value, which is either delta-seconds
+/// (`"120"`) or an HTTP-date (`"Wed, 21 Oct 2015 07:28:00 GMT"`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value.trim()).ok()?;
+    let now = std::time::SystemTime::now();
+    target.duration_since(now).ok()
+}
+
+fn apply_jitter(delay: Duration, jitter: bool) -> Duration {
+    if !jitter || delay.is_zero() {
+        return delay;
+    }
+    use rand::Rng;
+    let millis = rand::thread_rng().gen_range(0..=delay.as_millis() as u64);
+    Duration::from_millis(millis)
+}
+
 #[derive(Clone)]
 pub struct DeepgramTtsApi {
     client: Client,
@@ -82,24 +117,30 @@ impl DeepgramTtsApi {
                             trace!("Deepgram TTS request succeeded after {} retries", attempt);
                         }
                         return Ok(response);
-                    } else if response.status().as_u16() == 429 && attempt < max_retries {
-                        trace!("Deepgram API rate limited (429), waiting before retry");
-                        std::thread::sleep(delay);
-                        delay = std::cmp::min(
-                            Duration::from_millis(
-                                (delay.as_millis() as f64
-                                    * self.rate_limit_config.backoff_multiplier)
-                                    as u64,
-                            ),
-                            self.rate_limit_config.max_delay,
-                        );
-                        continue;
-                    } else if response.status().as_u16() >= 500 && attempt < max_retries {
+                    } else if (response.status().as_u16() == 429
+                        || response.status().as_u16() == 503
+                        || response.status().as_u16() >= 500)
+                        && attempt < max_retries
+                    {
                         trace!(
-                            "Deepgram API server error ({}), waiting before retry",
+                            "Deepgram API returned {}, waiting before retry",
                             response.status().as_u16()
                         );
-                        std::thread::sleep(delay);
+                        let retry_after = response
+                            .headers()
+                            .get("retry-after")
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(parse_retry_after);
+
+                        let computed_delay = match retry_after {
+                            Some(d) => std::cmp::min(d, self.rate_limit_config.max_delay),
+                            None => delay,
+                        };
+                        std::thread::sleep(apply_jitter(
+                            computed_delay,
+                            self.rate_limit_config.jitter,
+                        ));
+
                         delay = std::cmp::min(
                             Duration::from_millis(
                                 (delay.as_millis() as f64
@@ -145,6 +186,110 @@ impl DeepgramTtsApi {
         Ok(response.audio_data)
     }
 
+    /// Stream the synthesized audio as it arrives instead of buffering the
+    /// whole response. Retries (see `execute_with_retry`) only cover
+    /// establishing the response; once the first byte is read the stream
+    /// is committed and errors are propagated to the consumer rather than
+    /// silently restarting the request.
+    pub fn text_to_speech_stream(
+        &self,
+        request: &TextToSpeechRequest,
+        params: Option<&TextToSpeechParams>,
+    ) -> Result<mpsc::Receiver<Result<StreamEvent, TtsError>>, TtsError> {
+        let url = if let Some(p) = params {
+            format!(
+                "{}/{}/speak?{}",
+                self.base_url,
+                self.api_version,
+                p.to_query_string()
+            )
+        } else {
+            format!("{}/{}/speak", self.base_url, self.api_version)
+        };
+
+        trace!("Making streaming TTS request to: {}", url);
+
+        let request_clone = request.clone();
+        let client = self.clone();
+
+        let operation = || {
+            let req = client
+                .create_request(Method::POST, &url)
+                .json(&request_clone);
+            match req.send() {
+                Ok(response) => Ok(response),
+                Err(e) => Err(from_reqwest_error("TTS stream request failed", e)),
+            }
+        };
+
+        let response = self.execute_with_retry(operation)?;
+
+        if !response.status().is_success() {
+            return Err(tts_error_from_status(response.status()));
+        }
+
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let metadata = TtsResponseMetadata::from_response_headers(response.headers())
+                .unwrap_or_default();
+            if tx.send(Ok(StreamEvent::Metadata(metadata))).is_err() {
+                return;
+            }
+
+            let mut reader = response;
+            let mut buf = [0u8; 8192];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(Ok(StreamEvent::Chunk(buf[..n].to_vec()))).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(TtsError::NetworkError(format!(
+                            "Error reading TTS stream: {}",
+                            e
+                        ))));
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Fetch the live model catalog from Deepgram's models endpoint,
+    /// falling back to the bundled static list if the request fails so a
+    /// transient outage doesn't make voice discovery unusable.
+    pub fn list_voices(&self) -> Result<Vec<Model>, TtsError> {
+        let url = format!("{}/{}/models", self.base_url, self.api_version);
+
+        let operation = || {
+            self.create_request(Method::GET, &url)
+                .send()
+                .map_err(|e| from_reqwest_error("Deepgram list models request failed", e))
+        };
+
+        match self.execute_with_retry(operation) {
+            Ok(response) if response.status().is_success() => {
+                #[derive(Deserialize)]
+                struct ModelsResponse {
+                    #[serde(default)]
+                    tts: Vec<Model>,
+                }
+
+                match response.json::<ModelsResponse>() {
+                    Ok(parsed) if !parsed.tts.is_empty() => Ok(parsed.tts),
+                    _ => Ok(get_available_models()),
+                }
+            }
+            _ => Ok(get_available_models()),
+        }
+    }
+
     pub fn text_to_speech_with_metadata(
         &self,
         request: &TextToSpeechRequest,
@@ -306,6 +451,11 @@ pub struct Model {
     pub name: String,
     pub voice_id: String,
     pub language: String,
+    /// Every BCP-47 locale this voice can synthesize besides `language`,
+    /// when the live `/models` endpoint sends one (multilingual Aura
+    /// voices advertise this; legacy single-language models omit it).
+    #[serde(default)]
+    pub languages: Vec<String>,
     pub accent: String,
     pub gender: String,
     pub age: String,
@@ -313,12 +463,60 @@ pub struct Model {
     pub use_cases: Vec<String>,
 }
 
+/// Per-`voice_id` multilingual locale fallback for models the live
+/// `/models` endpoint doesn't tag with a `languages` list. Empty today:
+/// every model in [`get_available_models`] is genuinely English-only; add
+/// entries here as Deepgram ships multilingual Aura voices whose static
+/// fallback entry would otherwise need one.
+const STATIC_MULTILINGUAL_LOCALES: &[(&str, &[&str])] = &[];
+
+impl Model {
+    /// Parse `language` (e.g. `"en"`, derived from IDs like
+    /// `aura-asteria-en`) into a proper BCP-47 identifier.
+    pub fn language_identifier(&self) -> golem_tts::lang::LanguageIdentifier {
+        golem_tts::lang::LanguageIdentifier::parse(&self.language)
+    }
+
+    /// Whether this model's language matches `requested`, using
+    /// exact-then-language-only-then-neutral fallback.
+    pub fn matches_language(&self, requested: &golem_tts::lang::LanguageIdentifier) -> bool {
+        self.language_identifier().matches(requested)
+    }
+
+    /// Every locale this voice speaks besides its primary `language`,
+    /// normalized and deduplicated against it: `languages` from the API
+    /// when it sent one, otherwise [`STATIC_MULTILINGUAL_LOCALES`].
+    pub fn additional_languages(&self) -> Vec<String> {
+        let primary = crate::conversions::normalize_language_code(&self.language);
+
+        let locales: Vec<String> = if !self.languages.is_empty() {
+            self.languages.clone()
+        } else {
+            STATIC_MULTILINGUAL_LOCALES
+                .iter()
+                .find(|(id, _)| *id == self.voice_id)
+                .map(|(_, locales)| locales.iter().map(|l| l.to_string()).collect())
+                .unwrap_or_default()
+        };
+
+        let mut normalized: Vec<String> = locales
+            .iter()
+            .map(|l| crate::conversions::normalize_language_code(l))
+            .filter(|l| l != &primary)
+            .collect();
+        normalized.sort();
+        normalized.dedup();
+        normalized
+    }
+}
+
 pub fn get_available_models() -> Vec<Model> {
     vec![
         Model {
             name: "Aura Asteria".to_string(),
             voice_id: "aura-asteria-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Female".to_string(),
             age: "Adult".to_string(),
@@ -329,6 +527,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Luna".to_string(),
             voice_id: "aura-luna-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Female".to_string(),
             age: "Young Adult".to_string(),
@@ -339,6 +538,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Stella".to_string(),
             voice_id: "aura-stella-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Female".to_string(),
             age: "Adult".to_string(),
@@ -349,6 +549,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Athena".to_string(),
             voice_id: "aura-athena-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "British".to_string(),
             gender: "Female".to_string(),
             age: "Adult".to_string(),
@@ -359,6 +560,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Hera".to_string(),
             voice_id: "aura-hera-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Female".to_string(),
             age: "Adult".to_string(),
@@ -369,6 +571,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Orion".to_string(),
             voice_id: "aura-orion-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Male".to_string(),
             age: "Adult".to_string(),
@@ -379,6 +582,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Arcas".to_string(),
             voice_id: "aura-arcas-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Male".to_string(),
             age: "Middle Aged".to_string(),
@@ -389,6 +593,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Perseus".to_string(),
             voice_id: "aura-perseus-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "American".to_string(),
             gender: "Male".to_string(),
             age: "Adult".to_string(),
@@ -399,6 +604,7 @@ pub fn get_available_models() -> Vec<Model> {
             name: "Aura Angus".to_string(),
             voice_id: "aura-angus-en".to_string(),
             language: "en".to_string(),
+            languages: vec![],
             accent: "Irish".to_string(),
             gender: "Male".to_string(),
             age: "Young Adult".to_string(),
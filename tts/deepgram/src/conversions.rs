@@ -6,33 +6,81 @@ use golem_tts::golem::tts::types::{
 };
 use golem_tts::golem::tts::voices::VoiceInfo;
 
-pub fn estimate_audio_duration(audio_data: &[u8], sample_rate: u32) -> f32 {
-    if audio_data.is_empty() {
+/// Estimate playback duration from raw audio bytes, branching on
+/// `encoding` instead of assuming 16-bit PCM: `linear16` divides by
+/// `sample_rate * 2` bytes/sec, the 8-bit companded codecs (`mulaw`/
+/// `alaw`) by `sample_rate` bytes/sec, and compressed codecs (`mp3`/
+/// `aac`/`opus`) by `bit_rate` when the caller knows it (0.0 otherwise,
+/// rather than fabricating a number from an irrelevant sample rate). When
+/// `audio` is a RIFF/WAV container, only the `data` chunk's payload is
+/// counted so the 44-byte header doesn't inflate PCM estimates.
+pub fn estimate_audio_duration(audio: &[u8], encoding: &str, sample_rate: u32, bit_rate: Option<u32>) -> f32 {
+    if audio.is_empty() {
         return 0.0;
     }
 
-    let bytes_per_second = match sample_rate {
-        8000 => 16000,
-        16000 => 32000,
-        22050 => 44100,
-        24000 => 48000,
-        48000 => 96000,
-        _ => 48000,
-    };
+    let samples = wav_data_chunk(audio).unwrap_or(audio);
+
+    match encoding {
+        "linear16" => {
+            if sample_rate == 0 {
+                return 0.0;
+            }
+            samples.len() as f32 / (sample_rate as f32 * 2.0)
+        }
+        "mulaw" | "alaw" => {
+            if sample_rate == 0 {
+                return 0.0;
+            }
+            samples.len() as f32 / sample_rate as f32
+        }
+        "mp3" | "aac" | "opus" => match bit_rate {
+            Some(bit_rate) if bit_rate > 0 => (samples.len() as f32 * 8.0) / bit_rate as f32,
+            _ => 0.0,
+        },
+        _ => 0.0,
+    }
+}
+
+/// If `audio` starts with a RIFF/WAV header, scan past the 12-byte `RIFF`
+/// header and the chunk IDs that follow (each a 4-byte ID plus a
+/// little-endian 4-byte size) until the `data` chunk, returning just its
+/// payload. Returns `None` for anything that isn't a well-formed RIFF
+/// container, so callers fall back to treating `audio` as raw samples.
+fn wav_data_chunk(audio: &[u8]) -> Option<&[u8]> {
+    if audio.len() < 12 || &audio[0..4] != b"RIFF" || &audio[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut offset = 12;
+    while offset + 8 <= audio.len() {
+        let chunk_id = &audio[offset..offset + 4];
+        let chunk_size = u32::from_le_bytes(audio[offset + 4..offset + 8].try_into().ok()?) as usize;
+        let data_start = offset + 8;
 
-    (audio_data.len() as f32) / (bytes_per_second as f32)
+        if chunk_id == b"data" {
+            let data_end = (data_start + chunk_size).min(audio.len());
+            return Some(&audio[data_start..data_end]);
+        }
+
+        // Chunks are padded to an even number of bytes.
+        offset = data_start + chunk_size + (chunk_size % 2);
+    }
+
+    None
 }
 
 pub fn deepgram_model_to_voice_info(model: Model) -> VoiceInfo {
     let gender = parse_gender(&model.gender);
     let quality = infer_quality_from_model(&model.voice_id);
     let language = normalize_language_code(&model.language);
+    let additional_languages = model.additional_languages();
 
     VoiceInfo {
         id: model.voice_id.clone(),
         name: model.name.clone(),
         language: language.clone(),
-        additional_languages: vec![],
+        additional_languages,
         gender,
         quality,
         description: Some(format!(
@@ -48,7 +96,12 @@ pub fn deepgram_model_to_voice_info(model: Model) -> VoiceInfo {
         is_custom: false,
         is_cloned: false,
         preview_url: None,
-        use_cases: model.use_cases.clone(),
+        use_cases: model
+            .use_cases
+            .iter()
+            .chain(model.characteristics.iter())
+            .cloned()
+            .collect(),
     }
 }
 
@@ -68,12 +121,73 @@ pub fn infer_quality_from_model(voice_id: &str) -> VoiceQuality {
     }
 }
 
+/// Normalize a raw Deepgram language string (e.g. `"en"`, `"en-US"`) to its
+/// full BCP-47 form via the shared parser. Unlike the old lowercase-and-
+/// truncate version, this preserves region/script instead of collapsing
+/// every locale down to its bare primary subtag.
 pub fn normalize_language_code(code: &str) -> String {
-    match code.to_lowercase().as_str() {
-        "en-us" | "en-gb" | "en-au" | "en-ph" | "en-ie" => "en".to_string(),
-        "es-es" | "es-mx" | "es-co" | "es-419" => "es".to_string(),
-        _ => code.to_lowercase().chars().take(2).collect(),
+    golem_tts::lang::LanguageIdentifier::parse(code).to_string()
+}
+
+/// Macrolanguage/region equivalences [`language_tag_matches`] consults in
+/// addition to hierarchical subtag-prefix matching: a request for either
+/// side of a pair also matches voices tagged with the other.
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("no", "nb"),       // Norwegian macrolanguage <-> Bokmal
+    ("zh", "cmn"),      // Chinese macrolanguage <-> Mandarin
+    ("zh-hk", "yue-hk"), // Hong Kong Chinese request <-> Cantonese
+];
+
+/// Whether `candidate` (a voice's `language` or one of its
+/// `additional_languages`) satisfies a request for `requested`, using
+/// hierarchical BCP-47 subtag-prefix matching: `requested` (or its bare
+/// primary subtag) must equal `candidate` or be immediately followed by a
+/// `-` in it, so `en` matches `en-GB` but not `eng`. [`LANGUAGE_ALIASES`]
+/// extends this with macrolanguage/region equivalences AWS/Google don't
+/// need but Deepgram's `cmn`/`yue`/`nb` voice tags do.
+fn language_tag_matches(requested: &str, candidate: &str) -> bool {
+    let requested = requested.to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    let is_prefix_of_candidate = |tag: &str| -> bool {
+        candidate == tag || candidate.starts_with(&format!("{}-", tag))
+    };
+
+    if is_prefix_of_candidate(&requested) {
+        return true;
+    }
+
+    let requested_primary = requested.split('-').next().unwrap_or(&requested);
+    if requested_primary != requested && is_prefix_of_candidate(requested_primary) {
+        return true;
     }
+
+    LANGUAGE_ALIASES.iter().any(|&(a, b)| {
+        if requested == a || requested_primary == a {
+            is_prefix_of_candidate(b)
+        } else if requested == b || requested_primary == b {
+            is_prefix_of_candidate(a)
+        } else {
+            false
+        }
+    })
+}
+
+/// Filter `voices` to those whose `language` or any `additional_languages`
+/// entry satisfies a request for `requested`, per [`language_tag_matches`].
+/// Lets callers ask for a specific locale (e.g. `en-NZ`, `zh-HK`) without
+/// Deepgram-specific string munging at the call site.
+pub fn filter_voices_by_language(voices: &[VoiceInfo], requested: &str) -> Vec<VoiceInfo> {
+    voices
+        .iter()
+        .filter(|v| {
+            language_tag_matches(requested, &v.language)
+                || v.additional_languages
+                    .iter()
+                    .any(|l| language_tag_matches(requested, l))
+        })
+        .cloned()
+        .collect()
 }
 
 pub fn synthesis_options_to_tts_request(
@@ -161,16 +275,60 @@ fn audio_format_to_deepgram_params(
     }
 }
 
+/// Wrap raw `samples` (PCM or a companded codec) in a standard 44-byte
+/// RIFF/WAV header, for the providers (Deepgram included) that hand back
+/// headerless bytes even when the caller asked for a `wav` container.
+/// `encoding` picks the WAVE format code and bit depth: `linear16` is PCM
+/// (format 1) at 16 bits, `mulaw`/`alaw` are their companded codes (7/6)
+/// at 8 bits, matching the convention that MULAW/ALAW output ships inside
+/// a WAV wrapper.
+pub fn wrap_pcm_in_wav(samples: &[u8], encoding: &str, sample_rate: u32, channels: u16) -> Vec<u8> {
+    let (format_code, bits_per_sample): (u16, u16) = match encoding {
+        "mulaw" => (7, 8),
+        "alaw" => (6, 8),
+        _ => (1, 16),
+    };
+
+    let block_align = channels * (bits_per_sample / 8);
+    let byte_rate = sample_rate * block_align as u32;
+    let data_len = samples.len() as u32;
+
+    let mut wav = Vec::with_capacity(44 + samples.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&format_code.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(samples);
+    wav
+}
+
 pub fn audio_data_to_synthesis_result(
     audio_data: Vec<u8>,
     text: &str,
     encoding: &str,
     sample_rate: u32,
+    bit_rate: Option<u32>,
+    container: Option<&str>,
 ) -> SynthesisResult {
+    let audio_data = if container == Some("wav") && !audio_data.starts_with(b"RIFF") {
+        wrap_pcm_in_wav(&audio_data, encoding, sample_rate, 1)
+    } else {
+        audio_data
+    };
+
     let audio_size = audio_data.len() as u32;
     let character_count = text.chars().count() as u32;
     let word_count = text.split_whitespace().count() as u32;
-    let duration_seconds = estimate_audio_duration(&audio_data, sample_rate);
+    let duration_seconds = estimate_audio_duration(&audio_data, encoding, sample_rate, bit_rate);
 
     let metadata = Some(SynthesisMetadata {
         duration_seconds,
@@ -1,12 +1,30 @@
 // Deepgram Aura TTS provider - matching PR #90 architecture
+//
+// Following the tts-rs pattern of gating whole backends behind cargo
+// features, the capabilities beyond plain request/response synthesis are
+// each behind their own feature (on by default) so a component that only
+// needs `synthesize` can build with `--no-default-features` and drop the
+// streaming worker threads and long-form chaptering entirely:
+//   - "streaming": the HTTP chunked-streaming `StreamingGuest` impl
+//   - "long-form": chapter-chunked long-form synthesis
+mod chunking;
 mod client;
 mod conversions;
-
+#[cfg(feature = "long-form")]
+mod long_form;
+#[cfg(feature = "streaming")]
+mod segmentation;
+#[cfg(feature = "streaming")]
+mod streaming;
+
+use chunking::{text_to_speech_chunked, DEFAULT_MAX_CHARS};
 use client::{get_available_models, DeepgramTtsApi};
 use conversions::*;
+#[cfg(feature = "long-form")]
+use long_form::LongFormTracker;
 use golem_tts::config::with_config_key;
 use golem_tts::durability::{DurableTts, ExtendedGuest};
-use golem_tts::error::{invalid_text, unsupported, voice_not_found};
+use golem_tts::error::{invalid_text, lexicon_not_found, unsupported, voice_not_found};
 use golem_tts::golem::tts::advanced::{
     AudioSample, Guest as AdvancedGuest, LongFormJob, LongFormResult, PronunciationEntry,
     VoiceDesignParams,
@@ -15,12 +33,28 @@ use golem_tts::golem::tts::streaming::{Guest as StreamingGuest, StreamSession, S
 use golem_tts::golem::tts::synthesis::{
     Guest as SynthesisGuest, SynthesisOptions, ValidationResult,
 };
-use golem_tts::golem::tts::types::{SynthesisResult, TextInput, TimingInfo, TtsError};
+use golem_tts::golem::tts::types::{AudioChunk, SynthesisResult, TextInput, TimingInfo, TtsError};
 use golem_tts::golem::tts::voices::{Guest as VoicesGuest, LanguageInfo, VoiceFilter, VoiceInfo};
+use golem_tts::guest::AudioQueryGuest;
+use golem_tts::guest::VocabularyFilterGuest;
+use golem_tts::guest::DictionaryGuest;
+use golem_tts::lexicon::{Lexicon, LexiconEntry};
 use log::{debug, info, trace};
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(feature = "streaming")]
+use streaming::StreamManager;
 
 struct DeepgramComponent;
 
+thread_local! {
+    #[cfg(feature = "streaming")]
+    static STREAM_MANAGER: RefCell<Option<StreamManager>> = RefCell::new(None);
+    static LEXICONS: RefCell<HashMap<String, Lexicon>> = RefCell::new(HashMap::new());
+    #[cfg(feature = "long-form")]
+    static LONG_FORM: LongFormTracker = LongFormTracker::new();
+}
+
 impl DeepgramComponent {
     const API_KEY_ENV: &'static str = "DEEPGRAM_API_KEY";
 
@@ -29,22 +63,66 @@ impl DeepgramComponent {
             Ok(DeepgramTtsApi::new(api_key, "v1".to_string()))
         })
     }
+
+    #[cfg(feature = "streaming")]
+    fn with_stream_manager<R>(f: impl FnOnce(&StreamManager) -> Result<R, TtsError>) -> Result<R, TtsError> {
+        STREAM_MANAGER.with(|cell| {
+            if cell.borrow().is_none() {
+                let api_key = with_config_key(Self::API_KEY_ENV, Err, Ok)?;
+                *cell.borrow_mut() = Some(StreamManager::new(api_key));
+            }
+            f(cell.borrow().as_ref().unwrap())
+        })
+    }
+
+    /// Rewrite `text` using every lexicon registered so far. Lexicons are
+    /// applied in `HashMap` iteration order, so the order between lexicons
+    /// is not defined.
+    fn apply_lexicons(text: &str) -> String {
+        LEXICONS.with(|cell| {
+            let lexicons: Vec<Lexicon> = cell.borrow().values().cloned().collect();
+            golem_tts::lexicon::apply_all(&lexicons, text, false)
+        })
+    }
+
+    /// Apply `filter`'s `language` field via [`filter_voices_by_language`]'s
+    /// hierarchical/macrolanguage-aware matching, then hand the rest of the
+    /// filter (language cleared, since it's already been applied) to the
+    /// generic [`golem_tts::voice_filter`] pass.
+    fn apply_language_filter(
+        voices: Vec<VoiceInfo>,
+        mut filter: Option<VoiceFilter>,
+    ) -> (Vec<VoiceInfo>, Option<VoiceFilter>) {
+        let language = filter.as_mut().and_then(|f| f.language.take());
+        match language {
+            Some(language) => (filter_voices_by_language(&voices, &language), filter),
+            None => (voices, filter),
+        }
+    }
 }
 
 impl VoicesGuest for DeepgramComponent {
-    fn list_voices(_filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
+    fn list_voices(filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Deepgram: Listing voices");
-        let models = get_available_models();
-        Ok(models
+        let client = Self::create_client()?;
+        let voices: Vec<VoiceInfo> = client
+            .list_voices()?
             .into_iter()
             .map(deepgram_model_to_voice_info)
-            .collect())
+            .collect();
+        let (voices, filter) = Self::apply_language_filter(voices, filter);
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            None,
+            filter.as_ref(),
+        ))
     }
 
     fn get_voice(voice_id: String) -> Result<VoiceInfo, TtsError> {
         trace!("Deepgram: Getting voice {}", voice_id);
-        let models = get_available_models();
-        models
+        let client = Self::create_client()?;
+        client
+            .list_voices()?
             .into_iter()
             .find(|m| m.voice_id == voice_id)
             .map(deepgram_model_to_voice_info)
@@ -56,51 +134,41 @@ impl VoicesGuest for DeepgramComponent {
         filter: Option<VoiceFilter>,
     ) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Deepgram: Searching voices: {}", query);
-        let models = get_available_models();
-        let query_lower = query.to_lowercase();
-
-        Ok(models
+        let client = Self::create_client()?;
+        let voices: Vec<VoiceInfo> = client
+            .list_voices()?
             .into_iter()
-            .filter(|m| {
-                // Search in name, voice_id, characteristics, or use_cases
-                m.name.to_lowercase().contains(&query_lower)
-                    || m.voice_id.to_lowercase().contains(&query_lower)
-                    || m.characteristics
-                        .iter()
-                        .any(|c| c.to_lowercase().contains(&query_lower))
-                    || m.use_cases
-                        .iter()
-                        .any(|u| u.to_lowercase().contains(&query_lower))
-                    || m.accent.to_lowercase().contains(&query_lower)
-            })
-            .filter(|m| {
-                // Apply optional filters
-                if let Some(ref f) = filter {
-                    if let Some(ref lang) = f.language {
-                        if !m.language.starts_with(lang) {
-                            return false;
-                        }
-                    }
-                    if let Some(gender) = f.gender {
-                        let model_gender = parse_gender(&m.gender);
-                        if model_gender != gender {
-                            return false;
-                        }
-                    }
-                }
-                true
-            })
             .map(deepgram_model_to_voice_info)
-            .collect())
+            .collect();
+        let (voices, filter) = Self::apply_language_filter(voices, filter);
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            Some(&query),
+            filter.as_ref(),
+        ))
     }
 
     fn list_languages() -> Result<Vec<LanguageInfo>, TtsError> {
-        Ok(vec![LanguageInfo {
-            code: "en".to_string(),
-            name: "English".to_string(),
-            native_name: "English".to_string(),
-            voice_count: 12,
-        }])
+        let client = Self::create_client()?;
+        let models = client.list_voices()?;
+
+        let mut counts: std::collections::BTreeMap<String, u32> = std::collections::BTreeMap::new();
+        for model in &models {
+            *counts.entry(model.language_identifier().language).or_insert(0) += 1;
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|(code, voice_count)| {
+                let name = golem_tts::lang::language_display_name(&code);
+                LanguageInfo {
+                    code,
+                    name: name.clone(),
+                    native_name: name,
+                    voice_count,
+                }
+            })
+            .collect())
     }
 }
 
@@ -116,9 +184,16 @@ impl SynthesisGuest for DeepgramComponent {
         }
 
         let client = Self::create_client()?;
-        let (request, params) =
-            synthesis_options_to_tts_request(input.content.clone(), Some(options))?;
-        let response = client.text_to_speech_with_metadata(&request, params.as_ref())?;
+        let content = Self::apply_lexicons(&input.content);
+        let (_request, params) =
+            synthesis_options_to_tts_request(content.clone(), Some(options))?;
+        let response = text_to_speech_chunked(
+            &client,
+            &content,
+            params.as_ref(),
+            DEFAULT_MAX_CHARS,
+            false,
+        )?;
 
         // Convert to SynthesisResult with metadata
         let encoding = params
@@ -126,12 +201,16 @@ impl SynthesisGuest for DeepgramComponent {
             .and_then(|p| p.encoding.clone())
             .unwrap_or_else(|| "linear16".to_string());
         let sample_rate = params.as_ref().and_then(|p| p.sample_rate).unwrap_or(24000);
+        let bit_rate = params.as_ref().and_then(|p| p.bit_rate);
+        let container = params.as_ref().and_then(|p| p.container.as_deref());
 
         Ok(audio_data_to_synthesis_result(
             response.audio_data,
             &input.content,
             &encoding,
             sample_rate,
+            bit_rate,
+            container,
         ))
     }
 
@@ -172,33 +251,69 @@ impl SynthesisGuest for DeepgramComponent {
     }
 }
 
+#[cfg(feature = "streaming")]
+impl StreamingGuest for DeepgramComponent {
+    fn create_stream(options: SynthesisOptions) -> Result<StreamSession, TtsError> {
+        info!("Deepgram: Creating streaming session for voice {}", options.voice_id);
+        Self::with_stream_manager(|manager| manager.create_stream(options))
+    }
+
+    fn stream_send_text(session_id: String, input: TextInput) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.send_text(session_id, input))
+    }
+
+    fn stream_finish(session_id: String) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.finish(session_id))
+    }
+
+    fn stream_receive_chunk(session_id: String) -> Result<Option<AudioChunk>, TtsError> {
+        Self::with_stream_manager(|manager| manager.receive_chunk(session_id))
+    }
+
+    fn stream_has_pending(session_id: String) -> Result<bool, TtsError> {
+        Self::with_stream_manager(|manager| manager.has_pending(session_id))
+    }
+
+    fn stream_get_status(session_id: String) -> Result<StreamStatus, TtsError> {
+        Self::with_stream_manager(|manager| manager.get_status(session_id))
+    }
+
+    fn stream_close(session_id: String) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.close(session_id))
+    }
+}
+
+/// Built without the "streaming" feature: the `Guest` trait still needs an
+/// impl, but every method reports the capability as absent rather than
+/// linking the worker-thread transport in [`streaming`].
+#[cfg(not(feature = "streaming"))]
 impl StreamingGuest for DeepgramComponent {
     fn create_stream(_options: SynthesisOptions) -> Result<StreamSession, TtsError> {
-        Err(unsupported("Deepgram streaming not yet implemented"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
     fn stream_send_text(_session_id: String, _input: TextInput) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
     fn stream_finish(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
-    fn stream_receive_chunk(_session_id: String) -> Result<Option<Vec<u8>>, TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_receive_chunk(_session_id: String) -> Result<Option<AudioChunk>, TtsError> {
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
     fn stream_has_pending(_session_id: String) -> Result<bool, TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
     fn stream_get_status(_session_id: String) -> Result<StreamStatus, TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 
     fn stream_close(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("Deepgram built without the \"streaming\" feature"))
     }
 }
 
@@ -235,43 +350,124 @@ impl AdvancedGuest for DeepgramComponent {
     }
 
     fn create_lexicon(
-        _name: String,
-        _language: String,
-        _entries: Option<Vec<PronunciationEntry>>,
+        name: String,
+        language: String,
+        entries: Option<Vec<PronunciationEntry>>,
     ) -> Result<String, TtsError> {
-        Err(unsupported("Deepgram does not support lexicons"))
+        debug!("Deepgram: Creating lexicon '{}' ({})", name, language);
+        let entries = entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(LexiconEntry::from)
+            .collect();
+        let lexicon = Lexicon::new(name, language, entries);
+        let lexicon_id = lexicon.id.clone();
+        LEXICONS.with(|cell| cell.borrow_mut().insert(lexicon_id.clone(), lexicon));
+        Ok(lexicon_id)
+    }
+
+    fn add_lexicon_entry(lexicon_id: String, entry: PronunciationEntry) -> Result<(), TtsError> {
+        LEXICONS.with(|cell| {
+            let mut lexicons = cell.borrow_mut();
+            let lexicon = lexicons
+                .get_mut(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            lexicon.add_entry(entry.into());
+            Ok(())
+        })
     }
 
-    fn add_lexicon_entry(_lexicon_id: String, _entry: PronunciationEntry) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    fn remove_lexicon_entry(lexicon_id: String, word: String) -> Result<(), TtsError> {
+        LEXICONS.with(|cell| {
+            let mut lexicons = cell.borrow_mut();
+            let lexicon = lexicons
+                .get_mut(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            lexicon.remove_entry(&word);
+            Ok(())
+        })
     }
 
-    fn remove_lexicon_entry(_lexicon_id: String, _word: String) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    fn export_lexicon(lexicon_id: String) -> Result<String, TtsError> {
+        LEXICONS.with(|cell| {
+            let lexicons = cell.borrow();
+            let lexicon = lexicons
+                .get(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            lexicon
+                .export_json()
+                .map_err(|e| TtsError::InternalError(format!("Failed to export lexicon: {}", e)))
+        })
     }
 
-    fn export_lexicon(_lexicon_id: String) -> Result<String, TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    #[cfg(feature = "long-form")]
+    fn synthesize_long_form(
+        content: String,
+        voice_id: String,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
+    ) -> Result<LongFormJob, TtsError> {
+        info!(
+            "Deepgram: Starting long-form synthesis of {} chars for voice {}",
+            content.len(),
+            voice_id
+        );
+        let client = Self::create_client()?;
+        let (_request, params) = synthesis_options_to_tts_request(
+            content.clone(),
+            Some(SynthesisOptions {
+                voice_id,
+                audio_config: None,
+                voice_settings: None,
+                audio_effects: None,
+                model_version: None,
+                enable_timing: None,
+                enable_word_timing: None,
+                seed: None,
+                context: None,
+            }),
+        )?;
+        LONG_FORM.with(|tracker| {
+            tracker.synthesize_long_form(&client, &content, output_location, chapter_breaks, params.as_ref())
+        })
     }
 
+    #[cfg(not(feature = "long-form"))]
     fn synthesize_long_form(
         _content: String,
         _voice_id: String,
         _output_location: String,
         _chapter_breaks: Option<Vec<u32>>,
     ) -> Result<LongFormJob, TtsError> {
-        Err(unsupported("Deepgram does not support long-form synthesis"))
+        Err(unsupported("Deepgram built without the \"long-form\" feature"))
+    }
+
+    #[cfg(feature = "long-form")]
+    fn get_long_form_status(job_id: String) -> Result<LongFormResult, TtsError> {
+        LONG_FORM.with(|tracker| tracker.get_long_form_status(&job_id))
     }
 
+    #[cfg(not(feature = "long-form"))]
     fn get_long_form_status(_job_id: String) -> Result<LongFormResult, TtsError> {
-        Err(unsupported("Long-form not supported"))
+        Err(unsupported("Deepgram built without the \"long-form\" feature"))
     }
 
+    #[cfg(feature = "long-form")]
+    fn cancel_long_form(job_id: String) -> Result<(), TtsError> {
+        LONG_FORM.with(|tracker| tracker.cancel_long_form(&job_id))
+    }
+
+    #[cfg(not(feature = "long-form"))]
     fn cancel_long_form(_job_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Long-form not supported"))
+        Err(unsupported("Deepgram built without the \"long-form\" feature"))
     }
 }
 
+impl AudioQueryGuest for DeepgramComponent {}
+
+impl VocabularyFilterGuest for DeepgramComponent {}
+impl DictionaryGuest for DeepgramComponent {}
+
 impl ExtendedGuest for DeepgramComponent {}
 
 type DurableDeepgramComponent = DurableTts<DeepgramComponent>;
@@ -0,0 +1,230 @@
+//! Client-side long-form synthesis: splits `content` into segments bounded
+//! by Deepgram's 2000-character per-request limit, honoring `chapter_breaks`
+//! as hard split points, synthesizes each segment sequentially through
+//! `DeepgramTtsApi::text_to_speech_with_metadata`, and concatenates the
+//! resulting audio.
+//!
+//! There's no background execution in this component model, so a job runs
+//! to completion inside the `synthesize_long_form` call; the registry below
+//! exists so `get_long_form_status` can report real per-job progress and
+//! `cancel_long_form` can stop a job from being reported as still running.
+use crate::chunking::{
+    concatenation_error, split_text_into_fragments, DEFAULT_MAX_CHARS,
+};
+use crate::client::{DeepgramTtsApi, TextToSpeechParams, TextToSpeechRequest};
+use golem_tts::exports::golem::tts::advanced::{
+    LongFormJob as WitLongFormJob, LongFormResult as WitLongFormResult,
+};
+use golem_tts::golem::tts::types::TtsError as WitTtsError;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+struct LongFormJobRecord {
+    status: String,
+    total_segments: u32,
+    completed_segments: u32,
+    output_location: String,
+    audio_data: Vec<u8>,
+    error: Option<String>,
+    cancelled: bool,
+}
+
+pub struct LongFormTracker {
+    jobs: RefCell<HashMap<String, LongFormJobRecord>>,
+}
+
+impl LongFormTracker {
+    pub fn new() -> Self {
+        Self {
+            jobs: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn synthesize_long_form(
+        &self,
+        client: &DeepgramTtsApi,
+        content: &str,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
+        params: Option<&TextToSpeechParams>,
+    ) -> Result<WitLongFormJob, WitTtsError> {
+        let job_id = uuid::Uuid::new_v4().to_string();
+        let segments = split_into_segments(content, chapter_breaks.as_deref());
+        let total_segments = segments.len() as u32;
+
+        self.jobs.borrow_mut().insert(
+            job_id.clone(),
+            LongFormJobRecord {
+                status: "processing".to_string(),
+                total_segments,
+                completed_segments: 0,
+                output_location,
+                audio_data: Vec::new(),
+                error: None,
+                cancelled: false,
+            },
+        );
+
+        let encoding = params
+            .and_then(|p| p.encoding.clone())
+            .unwrap_or_else(|| "linear16".to_string());
+
+        // Fail fast rather than silently dropping every segment but the
+        // first: concatenating multiple fragments of a non-concatenatable
+        // encoding (see `concatenation_error`) would corrupt the result,
+        // so don't spend a single API call on a job that can't produce a
+        // valid output.
+        if let Some(err) = concatenation_error(segments.len(), &encoding, false) {
+            let mut jobs = self.jobs.borrow_mut();
+            let job = jobs.get_mut(&job_id).unwrap();
+            job.status = "failed".to_string();
+            job.error = Some(format!("{:?}", err));
+            return Ok(WitLongFormJob {
+                job_id,
+                status: job.status.clone(),
+                total_segments,
+            });
+        }
+
+        for segment in segments {
+            if self
+                .jobs
+                .borrow()
+                .get(&job_id)
+                .map(|job| job.cancelled)
+                .unwrap_or(true)
+            {
+                break;
+            }
+
+            let request = TextToSpeechRequest { text: segment };
+            match client.text_to_speech_with_metadata(&request, params) {
+                Ok(response) => {
+                    let mut jobs = self.jobs.borrow_mut();
+                    let job = jobs.get_mut(&job_id).unwrap();
+                    job.audio_data.extend_from_slice(&response.audio_data);
+                    job.completed_segments += 1;
+                }
+                Err(e) => {
+                    let mut jobs = self.jobs.borrow_mut();
+                    let job = jobs.get_mut(&job_id).unwrap();
+                    job.status = "failed".to_string();
+                    job.error = Some(format!("{:?}", e));
+                    break;
+                }
+            }
+        }
+
+        let status = {
+            let mut jobs = self.jobs.borrow_mut();
+            let job = jobs.get_mut(&job_id).unwrap();
+            if job.status == "processing" {
+                job.status = if job.completed_segments == job.total_segments {
+                    "completed".to_string()
+                } else {
+                    "cancelled".to_string()
+                };
+            }
+            job.status.clone()
+        };
+
+        Ok(WitLongFormJob {
+            job_id,
+            status,
+            total_segments,
+        })
+    }
+
+    pub fn get_long_form_status(&self, job_id: &str) -> Result<WitLongFormResult, WitTtsError> {
+        let jobs = self.jobs.borrow();
+        let job = jobs
+            .get(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+
+        let percent_complete = if job.total_segments == 0 {
+            100.0
+        } else {
+            (job.completed_segments as f32 / job.total_segments as f32) * 100.0
+        };
+
+        Ok(WitLongFormResult {
+            job_id: job_id.to_string(),
+            status: job.status.clone(),
+            percent_complete,
+            segments_completed: job.completed_segments,
+            total_segments: job.total_segments,
+            output_location: job.output_location.clone(),
+            audio_data: job.audio_data.clone(),
+            error: job.error.clone(),
+        })
+    }
+
+    pub fn cancel_long_form(&self, job_id: &str) -> Result<(), WitTtsError> {
+        let mut jobs = self.jobs.borrow_mut();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+        job.cancelled = true;
+        if job.status == "processing" {
+            job.status = "cancelled".to_string();
+        }
+        Ok(())
+    }
+}
+
+/// Split `content` on `chapter_breaks` (character offsets treated as hard
+/// split points), then further split each resulting chapter so no segment
+/// exceeds Deepgram's per-request limit.
+fn split_into_segments(content: &str, chapter_breaks: Option<&[u32]>) -> Vec<String> {
+    let breaks = match chapter_breaks {
+        Some(breaks) if !breaks.is_empty() => breaks,
+        _ => return split_text_into_fragments(content, DEFAULT_MAX_CHARS),
+    };
+
+    let chars: Vec<char> = content.chars().collect();
+    let mut offsets: Vec<usize> = breaks.iter().map(|b| (*b as usize).min(chars.len())).collect();
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let mut segments = Vec::new();
+    let mut start = 0usize;
+    for offset in offsets {
+        if offset > start {
+            let chapter: String = chars[start..offset].iter().collect();
+            segments.extend(split_text_into_fragments(&chapter, DEFAULT_MAX_CHARS));
+        }
+        start = offset;
+    }
+    if start < chars.len() {
+        let chapter: String = chars[start..].iter().collect();
+        segments.extend(split_text_into_fragments(&chapter, DEFAULT_MAX_CHARS));
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_chapter_breaks_falls_back_to_char_limit_splitting() {
+        let text = "a".repeat(3000);
+        let segments = split_into_segments(&text, None);
+        assert_eq!(segments, split_text_into_fragments(&text, DEFAULT_MAX_CHARS));
+    }
+
+    #[test]
+    fn chapter_breaks_are_hard_split_points() {
+        let text = "one two three four";
+        let segments = split_into_segments(text, Some(&[8]));
+        assert_eq!(segments, vec!["one two".to_string(), "three four".to_string()]);
+    }
+
+    #[test]
+    fn out_of_order_and_out_of_range_breaks_are_normalized() {
+        let text = "abcdefgh";
+        let segments = split_into_segments(text, Some(&[6, 2, 1000]));
+        assert_eq!(segments, vec!["ab".to_string(), "cdef".to_string(), "gh".to_string()]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Stable-segment buffering for incremental streaming input.
+//!
+//! Borrowed from the AWS transcriber's "stabilize partial results" idea:
+//! accumulate incoming text and only commit a segment once it has
+//! stabilized on a boundary, tracking a `committed_len` so each committed
+//! segment is emitted exactly once. Incoming text is appended as-is:
+//! fragments that repeat already-sent text are not detected, and the
+//! repeated text is synthesized again.
+use golem_tts::config::get_config_or_default;
+
+const SENTENCE_TERMINATORS: &[char] = &['.', '!', '?'];
+const CLAUSE_TERMINATORS: &[char] = &[',', ';', ':'];
+
+/// Default length of the unflushed tail that triggers clause-boundary
+/// segmentation under [`Granularity::High`].
+pub const DEFAULT_MAX_CHARS: usize = 200;
+
+/// How eagerly buffered text is flushed to the synthesis backend. `Low`
+/// only flushes on sentence terminators, favoring fewer, more natural
+/// requests; `High` also falls back to clause boundaries once `max_chars`
+/// is exceeded, trading smaller chunks for lower latency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Low,
+    High,
+}
+
+impl Granularity {
+    /// Read the granularity knob from `DEEPGRAM_STREAM_GRANULARITY`
+    /// ("low" or "high", default "low"). `SynthesisOptions` has no field
+    /// for this yet, so it's exposed the same way other provider-specific
+    /// thresholds are until the WIT contract grows one.
+    pub fn from_config() -> Self {
+        match get_config_or_default("DEEPGRAM_STREAM_GRANULARITY", "low").as_str() {
+            "high" => Granularity::High,
+            _ => Granularity::Low,
+        }
+    }
+}
+
+/// Accumulates incremental `send_text` fragments and yields stabilized
+/// segments as soon as the unflushed tail contains a boundary.
+pub struct SegmentBuffer {
+    buffer: String,
+    committed_len: usize,
+    granularity: Granularity,
+    max_chars: usize,
+}
+
+impl SegmentBuffer {
+    pub fn new(granularity: Granularity) -> Self {
+        Self {
+            buffer: String::new(),
+            committed_len: 0,
+            granularity,
+            max_chars: DEFAULT_MAX_CHARS,
+        }
+    }
+
+    /// Append newly received text and return zero or more segments that
+    /// have now stabilized. The unflushed remainder stays buffered so it
+    /// can absorb more text before it commits.
+    pub fn push(&mut self, content: &str) -> Vec<String> {
+        self.buffer.push_str(content);
+
+        let mut segments = Vec::new();
+        while let Some(end) = self.find_boundary() {
+            let segment_end = self.committed_len + end;
+            let segment = self.buffer[self.committed_len..segment_end].trim();
+            if !segment.is_empty() {
+                segments.push(segment.to_string());
+            }
+            self.committed_len = segment_end;
+        }
+        segments
+    }
+
+    /// Flush whatever remains in the buffer, regardless of boundaries.
+    /// Call once on `finish` to emit the final, necessarily-unterminated
+    /// segment.
+    pub fn flush_remaining(&mut self) -> Option<String> {
+        let tail = self.buffer[self.committed_len..].trim();
+        if tail.is_empty() {
+            return None;
+        }
+        let segment = tail.to_string();
+        self.committed_len = self.buffer.len();
+        Some(segment)
+    }
+
+    /// Find the end offset (exclusive, byte index relative to
+    /// `committed_len`) of the longest stabilized prefix of the unflushed
+    /// tail, or `None` if it hasn't stabilized yet.
+    fn find_boundary(&self) -> Option<usize> {
+        let tail = &self.buffer[self.committed_len..];
+        if tail.is_empty() {
+            return None;
+        }
+
+        if let Some(pos) = tail.rfind(SENTENCE_TERMINATORS) {
+            return Some(pos + 1);
+        }
+
+        if self.granularity == Granularity::High && tail.chars().count() > self.max_chars {
+            if let Some(pos) = tail.rfind(CLAUSE_TERMINATORS) {
+                return Some(pos + 1);
+            }
+        }
+
+        None
+    }
+}
@@ -1,9 +1,28 @@
-//! WebSocket streaming implementation for Deepgram Aura TTS
+//! HTTP chunked-streaming implementation for Deepgram Aura TTS.
 //!
-//! Note: This implementation requires a WebSocket client library that works in WASI.
-//! As of WASI 0.23, WebSocket support may be limited. This is a reference implementation
-//! that shows the intended structure.
-
+//! WASI 0.23 has no WebSocket support, so this does not open a socket to
+//! Deepgram's `/v1/speak` endpoint. Instead `send_text` runs incoming text
+//! through a [`SegmentBuffer`] that stabilizes it into sentence- (or
+//! clause-) sized segments, and each stabilized segment is handed to a
+//! per-session worker thread that POSTs it through
+//! [`DeepgramTtsApi::text_to_speech_stream`] and drains the chunked HTTP
+//! response incrementally, collecting the segment's audio frames and
+//! pushing them onto a shared `VecDeque` once the segment has been read in
+//! full. Segments are processed one at a time so audio
+//! comes back in the order the text was spoken, and `receive_chunk` /
+//! `has_pending` pop from that queue. A WebSocket transport remains a
+//! future option once WASI grows socket support, but it buys nothing today
+//! since the audio still can't start flowing before the round trip opens.
+//!
+//! Each segment is synthesized through [`retry_with_config`], which builds
+//! a fresh request on every attempt rather than trying to resume a broken
+//! one: on a retryable error the segment's audio collected so far is
+//! discarded and the whole segment is resent, so nothing is ever
+//! duplicated or lost. `reconnects`/`last_error` track this for
+//! diagnostics; the streaming WIT interface has no getters for them, so
+//! they're surfaced via the component log rather than `get_status`.
+use crate::client::{DeepgramTtsApi, StreamEvent, TextToSpeechParams, TextToSpeechRequest};
+use crate::segmentation::{Granularity, SegmentBuffer};
 use golem_tts::exports::golem::tts::streaming::{
     StreamSession as WitStreamSession, StreamStatus as WitStreamStatus,
 };
@@ -11,39 +30,51 @@ use golem_tts::exports::golem::tts::synthesis::SynthesisOptions as WitSynthesisO
 use golem_tts::golem::tts::types::{
     AudioChunk as WitAudioChunk, TextInput as WitTextInput, TtsError as WitTtsError,
 };
-use std::collections::HashMap;
+use golem_tts::retry::{retry_with_config, RetryConfig};
+use log::warn;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Sender};
 use std::sync::{Arc, Mutex};
 
-// TODO: Add WebSocket library once WASI 0.3 becomes available
-// Current WASI 0.23 has limited WebSocket support
+#[derive(Clone, PartialEq)]
+enum StreamStatusInternal {
+    Active,
+    Finished,
+    Error(String),
+}
 
-pub struct StreamManager {
-    sessions: Arc<Mutex<HashMap<String, StreamSessionData>>>,
-    api_key: String,
+/// A unit of work handed to the per-session worker thread.
+enum SegmentMsg {
+    Text(String),
+    Finish,
 }
 
 struct StreamSessionData {
-    session_id: String,
     model: String,
     encoding: String,
     sample_rate: u32,
-    status: StreamStatusInternal,
-    pending_chunks: Vec<Vec<u8>>,
+    segmenter: Mutex<SegmentBuffer>,
+    worker: Sender<SegmentMsg>,
+    inbound: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    status: Arc<Mutex<StreamStatusInternal>>,
+    /// Number of retry attempts made across the session.
+    reconnects: Arc<Mutex<u32>>,
+    /// The most recent error seen on any attempt, retryable or not. Kept
+    /// even after a retry recovers, as a breadcrumb for why a reconnect
+    /// happened.
+    last_error: Arc<Mutex<Option<String>>>,
 }
 
-#[derive(Clone)]
-enum StreamStatusInternal {
-    Connecting,
-    Active,
-    Finished,
-    Error(String),
+pub struct StreamManager {
+    sessions: Arc<Mutex<HashMap<String, StreamSessionData>>>,
+    client: DeepgramTtsApi,
 }
 
 impl StreamManager {
     pub fn new(api_key: String) -> Self {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
-            api_key,
+            client: DeepgramTtsApi::new(api_key, "v1".to_string()),
         }
     }
 
@@ -51,48 +82,119 @@ impl StreamManager {
         &self,
         options: WitSynthesisOptions,
     ) -> Result<WitStreamSession, WitTtsError> {
-        // WebSocket endpoint: wss://api.deepgram.com/v1/speak?model=aura-asteria-en&encoding=linear16&sample_rate=24000
-
         let session_id = uuid::Uuid::new_v4().to_string();
-        let model = options.voice_id.clone();
-        let encoding = "linear16".to_string(); // Could map from AudioFormat
+        let model = if options.voice_id.is_empty() {
+            "aura-asteria-en".to_string()
+        } else {
+            options.voice_id.clone()
+        };
+        let encoding = "linear16".to_string();
         let sample_rate = options
             .audio_config
             .as_ref()
             .and_then(|c| c.sample_rate)
             .unwrap_or(24000);
 
-        // TODO: Establish WebSocket connection
-        // For now, returning error indicating WebSocket limitation
+        let inbound = Arc::new(Mutex::new(VecDeque::new()));
+        let status = Arc::new(Mutex::new(StreamStatusInternal::Active));
+        let reconnects = Arc::new(Mutex::new(0u32));
+        let last_error = Arc::new(Mutex::new(None));
+        let (tx, rx) = mpsc::channel::<SegmentMsg>();
 
-        return Err(WitTtsError::UnsupportedOperation(
-            "WebSocket streaming requires WASI 0.3+ - not yet available in WASI 0.23. \
-             Use REST API synthesis as alternative."
-                .to_string(),
-        ));
+        let client = self.client.clone();
+        let worker_model = model.clone();
+        let worker_encoding = encoding.clone();
+        let worker_inbound = inbound.clone();
+        let worker_status = status.clone();
+        let worker_reconnects = reconnects.clone();
+        let worker_last_error = last_error.clone();
 
-        /* Reference implementation for when WebSocket is available:
+        std::thread::spawn(move || {
+            for msg in rx {
+                let text = match msg {
+                    SegmentMsg::Text(text) => text,
+                    SegmentMsg::Finish => break,
+                };
 
-        let ws_url = format!(
-            "wss://api.deepgram.com/v1/speak?model={}&encoding={}&sample_rate={}",
-            model, encoding, sample_rate
-        );
+                let mut attempt = 0u32;
+                let outcome = retry_with_config(&RetryConfig::default(), || {
+                    if attempt > 0 {
+                        *worker_reconnects.lock().unwrap() += 1;
+                        warn!("Deepgram stream reconnect attempt {} for segment", attempt);
+                    }
+                    attempt += 1;
 
-        // Connect to WebSocket with API key in header
-        // let ws_client = WebSocketClient::connect(&ws_url)
-        //     .header("Authorization", &format!("Token {}", self.api_key))
-        //     .connect()?;
+                    let request = TextToSpeechRequest {
+                        text: text.clone(),
+                    };
+                    let params = TextToSpeechParams {
+                        model: Some(worker_model.clone()),
+                        encoding: Some(worker_encoding.clone()),
+                        container: None,
+                        sample_rate: Some(sample_rate),
+                        bit_rate: None,
+                    };
+
+                    // Collect into a local buffer rather than pushing onto
+                    // `inbound` as events arrive: if this attempt fails
+                    // partway through, the whole segment is resent from
+                    // scratch, so any chunks already read here must be
+                    // discarded instead of left queued for playback.
+                    let record_error = |e: WitTtsError| {
+                        *worker_last_error.lock().unwrap() = Some(format!("{:?}", e));
+                        e
+                    };
+
+                    let mut chunks = Vec::new();
+                    let events = client
+                        .text_to_speech_stream(&request, Some(&params))
+                        .map_err(record_error)?;
+                    for event in events {
+                        match event.map_err(record_error)? {
+                            StreamEvent::Metadata(_) => {}
+                            StreamEvent::Chunk(bytes) => chunks.push(bytes),
+                        }
+                    }
+                    Ok(chunks)
+                });
+
+                match outcome {
+                    Ok(chunks) => {
+                        worker_inbound.lock().unwrap().extend(chunks);
+                    }
+                    Err(e) => {
+                        *worker_status.lock().unwrap() =
+                            StreamStatusInternal::Error(format!("{:?}", e));
+                        return;
+                    }
+                }
+            }
+            let reconnects = *worker_reconnects.lock().unwrap();
+            if reconnects > 0 {
+                warn!(
+                    "Deepgram stream finished after {} reconnect(s); last error: {:?}",
+                    reconnects,
+                    *worker_last_error.lock().unwrap()
+                );
+            }
+            *worker_status.lock().unwrap() = StreamStatusInternal::Finished;
+        });
 
         let session_data = StreamSessionData {
-            session_id: session_id.clone(),
             model: model.clone(),
             encoding: encoding.clone(),
             sample_rate,
-            status: StreamStatusInternal::Active,
-            pending_chunks: Vec::new(),
+            segmenter: Mutex::new(SegmentBuffer::new(Granularity::from_config())),
+            worker: tx,
+            inbound,
+            status,
+            reconnects,
+            last_error,
         };
-
-        self.sessions.lock().unwrap().insert(session_id.clone(), session_data);
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), session_data);
 
         Ok(WitStreamSession {
             session_id,
@@ -100,79 +202,103 @@ impl StreamManager {
             encoding,
             sample_rate,
         })
-        */
     }
 
+    /// Feed `input` through the session's [`SegmentBuffer`] and hand off
+    /// any segment that just stabilized to the worker thread.
     pub fn send_text(&self, session_id: String, input: WitTextInput) -> Result<(), WitTtsError> {
-        // TODO: Send text over WebSocket
-        Err(WitTtsError::UnsupportedOperation(
-            "WebSocket not available in WASI 0.23".to_string(),
-        ))
-
-        /* Reference implementation:
         let sessions = self.sessions.lock().unwrap();
-        let session = sessions.get(&session_id)
+        let session = sessions
+            .get(&session_id)
             .ok_or_else(|| WitTtsError::SessionNotFound(session_id.clone()))?;
 
-        // Send text to WebSocket
-        // ws_client.send_text(&input.content)?;
-
+        let segments = session.segmenter.lock().unwrap().push(&input.content);
+        for segment in segments {
+            session
+                .worker
+                .send(SegmentMsg::Text(segment))
+                .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))?;
+        }
         Ok(())
-        */
     }
 
     pub fn finish(&self, session_id: String) -> Result<(), WitTtsError> {
-        // TODO: Send finish signal and close WebSocket
-        Err(WitTtsError::UnsupportedOperation(
-            "WebSocket not available in WASI 0.23".to_string(),
-        ))
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.clone()))?;
+
+        if let Some(tail) = session.segmenter.lock().unwrap().flush_remaining() {
+            session
+                .worker
+                .send(SegmentMsg::Text(tail))
+                .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))?;
+        }
+
+        session
+            .worker
+            .send(SegmentMsg::Finish)
+            .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))
     }
 
     pub fn receive_chunk(&self, session_id: String) -> Result<Option<WitAudioChunk>, WitTtsError> {
-        // TODO: Receive audio chunks from WebSocket
-        Err(WitTtsError::UnsupportedOperation(
-            "WebSocket not available in WASI 0.23".to_string(),
-        ))
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
+
+        Ok(session
+            .inbound
+            .lock()
+            .unwrap()
+            .pop_front()
+            .map(|data| WitAudioChunk {
+                data,
+                is_final: false,
+                sequence_number: 0,
+                timing_info: None,
+            }))
     }
 
     pub fn has_pending(&self, session_id: String) -> Result<bool, WitTtsError> {
         let sessions = self.sessions.lock().unwrap();
-        if let Some(session) = sessions.get(&session_id) {
-            Ok(!session.pending_chunks.is_empty())
-        } else {
-            Ok(false)
-        }
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
+
+        let has_buffered = !session.inbound.lock().unwrap().is_empty();
+        let still_active = matches!(*session.status.lock().unwrap(), StreamStatusInternal::Active);
+        Ok(has_buffered || still_active)
     }
 
+    /// `reconnects` and `last_error` aren't part of this WIT struct's fixed
+    /// shape, so they stay internal diagnostics (see the module doc); only
+    /// a terminal, retries-exhausted error is reflected here.
     pub fn get_status(&self, session_id: String) -> Result<WitStreamStatus, WitTtsError> {
         let sessions = self.sessions.lock().unwrap();
         let session = sessions
             .get(&session_id)
             .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
 
-        let status_str = match &session.status {
-            StreamStatusInternal::Connecting => "connecting",
-            StreamStatusInternal::Active => "active",
-            StreamStatusInternal::Finished => "finished",
-            StreamStatusInternal::Error(_) => "error",
-        };
+        let status = session.status.lock().unwrap().clone();
+        let has_pending = !session.inbound.lock().unwrap().is_empty();
 
-        let error = match &session.status {
-            StreamStatusInternal::Error(msg) => Some(msg.clone()),
-            _ => None,
+        let (status_str, error) = match &status {
+            StreamStatusInternal::Active => ("active", None),
+            StreamStatusInternal::Finished => ("finished", None),
+            StreamStatusInternal::Error(msg) => ("error", Some(msg.clone())),
         };
 
         Ok(WitStreamStatus {
             status: status_str.to_string(),
-            is_active: matches!(session.status, StreamStatusInternal::Active),
-            has_pending_chunks: !session.pending_chunks.is_empty(),
+            is_active: status == StreamStatusInternal::Active,
+            has_pending_chunks: has_pending,
             error,
         })
     }
 
     pub fn close(&self, session_id: String) -> Result<(), WitTtsError> {
-        let mut sessions = self.sessions.lock().unwrap();
-        sessions.remove(&session_id);
+        self.sessions.lock().unwrap().remove(&session_id);
         Ok(())
     }
 }
@@ -0,0 +1,92 @@
+// Long-text chunking and audio concatenation for ElevenLabs, whose
+// `/text-to-speech` endpoint rejects requests whose text exceeds roughly
+// 5000 characters.
+use crate::client::{ElevenLabsClient, SynthesisResponse};
+use golem_tts::golem::tts::types::TtsError;
+
+/// Default fragment size, equal to ElevenLabs' roughly 5000-character per-request limit.
+pub const DEFAULT_MAX_CHUNK_CHARS: usize = 5000;
+
+/// Split `text` into fragments no larger than `max_chars`, breaking on
+/// whitespace where possible so words are never split across a boundary.
+///
+/// Whitespace is first canonicalized (trimmed, runs collapsed to a single
+/// space) so fragment boundaries are deterministic regardless of the
+/// caller's formatting.
+pub fn split_text_into_fragments(text: &str, max_chars: usize) -> Vec<String> {
+    let canonical = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if canonical.is_empty() {
+        return vec![];
+    }
+
+    if canonical.chars().count() <= max_chars {
+        return vec![canonical];
+    }
+
+    let mut fragments = Vec::new();
+    let mut remaining: Vec<char> = canonical.chars().collect();
+
+    while remaining.len() > max_chars {
+        let window: Vec<char> = remaining[..max_chars + 1].to_vec();
+        let break_at = window
+            .iter()
+            .rposition(|c| *c == ' ')
+            .unwrap_or(max_chars);
+
+        let fragment: String = window[..break_at].iter().collect();
+        fragments.push(fragment);
+
+        let skip = if break_at < window.len() && window[break_at] == ' ' {
+            break_at + 1
+        } else {
+            break_at
+        };
+        remaining = remaining[skip..].to_vec();
+    }
+
+    if !remaining.is_empty() {
+        fragments.push(remaining.into_iter().collect());
+    }
+
+    fragments
+}
+
+/// Synthesize `text` as one or more fragments of at most `max_chunk_chars`
+/// characters each, synthesizing sequentially and concatenating the raw MP3
+/// byte streams into a single response with summed metadata.
+pub fn synthesize_long(
+    client: &ElevenLabsClient,
+    text: &str,
+    voice_id: &str,
+    max_chunk_chars: usize,
+) -> Result<SynthesisResponse, TtsError> {
+    let fragments = split_text_into_fragments(text, max_chunk_chars);
+    if fragments.is_empty() {
+        return Err(TtsError::InvalidText("Text cannot be empty".to_string()));
+    }
+
+    let mut merged_audio = Vec::new();
+    let mut merged_metadata = None;
+
+    for fragment in fragments {
+        let response = client.text_to_speech(&fragment, voice_id)?;
+        merged_audio.extend_from_slice(&response.audio_data);
+
+        merged_metadata = Some(match merged_metadata {
+            None => response.metadata,
+            Some(mut acc) => {
+                acc.character_count += response.metadata.character_count;
+                acc.word_count += response.metadata.word_count;
+                acc.audio_size_bytes += response.metadata.audio_size_bytes;
+                acc.duration_seconds += response.metadata.duration_seconds;
+                acc
+            }
+        });
+    }
+
+    Ok(SynthesisResponse {
+        audio_data: merged_audio,
+        metadata: merged_metadata.unwrap(),
+    })
+}
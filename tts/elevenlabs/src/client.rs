@@ -7,8 +7,16 @@ use golem_tts::golem::tts::types::{SynthesisMetadata, TtsError};
 use log::trace;
 use reqwest::{Client, Method, RequestBuilder, Response};
 use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::mpsc;
 use std::time::Duration;
 
+/// A raw audio frame from `text_to_speech_stream`. ElevenLabs' streaming
+/// endpoint has no header-derived metadata the way Deepgram's does, so
+/// unlike `StreamEvent` over there this is just the chunk.
+#[derive(Debug)]
+pub struct StreamChunk(pub Vec<u8>);
+
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub max_retries: u32,
@@ -28,6 +36,18 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Parse a `Retry-After` header value, which is either delta-seconds
+/// (`"120"`) or an HTTP-date (`"Wed, 21 Oct 2015 07:28:00 GMT"`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value.trim()).ok()?;
+    let now = std::time::SystemTime::now();
+    target.duration_since(now).ok()
+}
+
 #[derive(Clone)]
 pub struct ElevenLabsClient {
     client: Client,
@@ -73,21 +93,24 @@ impl ElevenLabsClient {
                 Ok(response) => {
                     if response.status().is_success() {
                         return Ok(response);
-                    } else if response.status().as_u16() == 429 && attempt < max_retries {
-                        trace!("ElevenLabs rate limited, retrying");
-                        std::thread::sleep(delay);
-                        delay = std::cmp::min(
-                            Duration::from_millis(
-                                (delay.as_millis() as f64
-                                    * self.rate_limit_config.backoff_multiplier)
-                                    as u64,
-                            ),
-                            self.rate_limit_config.max_delay,
+                    } else if (response.status().as_u16() == 429
+                        || response.status().as_u16() >= 500)
+                        && attempt < max_retries
+                    {
+                        trace!(
+                            "ElevenLabs returned {}, waiting before retry",
+                            response.status().as_u16()
                         );
-                        continue;
-                    } else if response.status().as_u16() >= 500 && attempt < max_retries {
-                        trace!("ElevenLabs server error, retrying");
-                        std::thread::sleep(delay);
+                        let retry_after = response
+                            .headers()
+                            .get("retry-after")
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(parse_retry_after);
+                        let wait = match retry_after {
+                            Some(d) => std::cmp::min(d, self.rate_limit_config.max_delay),
+                            None => delay,
+                        };
+                        std::thread::sleep(wait);
                         delay = std::cmp::min(
                             Duration::from_millis(
                                 (delay.as_millis() as f64
@@ -175,6 +198,99 @@ impl ElevenLabsClient {
         })
     }
 
+    /// Stream synthesized audio from the `/stream` endpoint as it arrives
+    /// instead of buffering the whole response, the same way Deepgram's
+    /// `text_to_speech_stream` does. Retries only cover establishing the
+    /// response; once the first byte is read the stream is committed and
+    /// errors are propagated to the consumer rather than silently
+    /// restarting the request.
+    pub fn text_to_speech_stream(
+        &self,
+        text: &str,
+        voice_id: &str,
+    ) -> Result<mpsc::Receiver<Result<StreamChunk, TtsError>>, TtsError> {
+        let url = format!("{}/v1/text-to-speech/{}/stream", self.base_url, voice_id);
+
+        #[derive(Serialize)]
+        struct Request {
+            text: String,
+            model_id: String,
+        }
+
+        let body = Request {
+            text: text.to_string(),
+            model_id: "eleven_monolingual_v1".to_string(),
+        };
+
+        let response = self.execute_with_retry(|| {
+            self.create_request(Method::POST, &url)
+                .json(&body)
+                .send()
+                .map_err(|e| from_reqwest_error("ElevenLabs text_to_speech_stream", e))
+        })?;
+
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let mut reader = response;
+            let mut buf = [0u8; 8192];
+            loop {
+                match reader.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        if tx.send(Ok(StreamChunk(buf[..n].to_vec()))).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(TtsError::NetworkError(format!(
+                            "Error reading ElevenLabs stream: {}",
+                            e
+                        ))));
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    /// Synthesize via the `with-timestamps` endpoint, which returns
+    /// per-character alignment data alongside the base64-encoded audio.
+    pub fn text_to_speech_with_timestamps(
+        &self,
+        text: &str,
+        voice_id: &str,
+    ) -> Result<AlignmentResponse, TtsError> {
+        let url = format!(
+            "{}/v1/text-to-speech/{}/with-timestamps",
+            self.base_url, voice_id
+        );
+
+        #[derive(Serialize)]
+        struct Request {
+            text: String,
+            model_id: String,
+        }
+
+        let body = Request {
+            text: text.to_string(),
+            model_id: "eleven_monolingual_v1".to_string(),
+        };
+
+        let response = self.execute_with_retry(|| {
+            self.create_request(Method::POST, &url)
+                .json(&body)
+                .send()
+                .map_err(|e| from_reqwest_error("ElevenLabs text_to_speech_with_timestamps", e))
+        })?;
+
+        response
+            .json::<AlignmentResponse>()
+            .map_err(|e| from_reqwest_error("Parsing ElevenLabs alignment response", e))
+    }
+
     pub fn list_voices(&self) -> Result<Vec<Voice>, TtsError> {
         let url = format!("{}/v1/voices", self.base_url);
 
@@ -203,6 +319,20 @@ pub struct SynthesisResponse {
     pub metadata: SynthesisMetadata,
 }
 
+/// Per-character timing alignment returned by the `with-timestamps` endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Alignment {
+    pub characters: Vec<String>,
+    pub character_start_times_seconds: Vec<f32>,
+    pub character_end_times_seconds: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AlignmentResponse {
+    pub audio_base64: String,
+    pub alignment: Alignment,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Voice {
     pub voice_id: String,
@@ -213,10 +343,124 @@ pub struct Voice {
     pub preview_url: Option<String>,
 }
 
+/// Estimate MP3 audio duration by walking its frame headers and summing
+/// `samples_per_frame / sample_rate` for every frame found, which is exact
+/// regardless of the bitrate ElevenLabs actually used. Falls back to a
+/// byte-rate heuristic if the payload doesn't parse as MP3 (e.g. empty or
+/// malformed audio).
 fn estimate_audio_duration(audio_data: &[u8]) -> f32 {
-    // MP3 at 128kbps ~= 16000 bytes/second
     if audio_data.is_empty() {
         return 0.0;
     }
+
+    if let Some(duration) = mp3_duration_from_frames(audio_data) {
+        return duration;
+    }
+
+    // MP3 at 128kbps ~= 16000 bytes/second
     (audio_data.len() as f32) / 16000.0
 }
+
+struct Mp3Frame {
+    sample_rate: u32,
+    samples_per_frame: u32,
+    frame_length: usize,
+}
+
+/// Sum the duration of every MPEG Layer III frame found in `data`, skipping
+/// a leading ID3v2 tag if present. Returns `None` if no valid frame is
+/// found, so the caller can fall back to a byte-rate estimate.
+fn mp3_duration_from_frames(data: &[u8]) -> Option<f32> {
+    let mut pos = skip_id3v2_tag(data);
+    let mut total_seconds = 0.0f32;
+    let mut frames_found = 0u32;
+
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF || (data[pos + 1] & 0xE0) != 0xE0 {
+            pos += 1;
+            continue;
+        }
+
+        match parse_mp3_frame(&data[pos..pos + 4]) {
+            Some(frame) => {
+                total_seconds += frame.samples_per_frame as f32 / frame.sample_rate as f32;
+                frames_found += 1;
+                pos += frame.frame_length;
+            }
+            // Sync-looking byte pair that didn't decode to a valid header;
+            // resync one byte forward rather than trusting it as a frame.
+            None => pos += 1,
+        }
+    }
+
+    if frames_found > 0 {
+        Some(total_seconds)
+    } else {
+        None
+    }
+}
+
+/// Decode an MPEG audio frame header (the 4 bytes starting at the `0xFFE`
+/// sync word). Only Layer III is handled since that's all ElevenLabs emits;
+/// anything else, or a reserved/free bitrate or sample-rate index, is
+/// treated as a false sync match.
+fn parse_mp3_frame(header: &[u8]) -> Option<Mp3Frame> {
+    let version_bits = (header[1] >> 3) & 0x03;
+    let layer_bits = (header[1] >> 1) & 0x03;
+    if layer_bits != 0x01 {
+        return None;
+    }
+
+    let bitrate_index = (header[2] >> 4) & 0x0F;
+    let sample_rate_index = (header[2] >> 2) & 0x03;
+    let padding = ((header[2] >> 1) & 0x01) as usize;
+    if bitrate_index == 0 || bitrate_index == 0x0F || sample_rate_index == 0x03 {
+        return None;
+    }
+
+    let is_mpeg1 = version_bits == 0x03;
+    let bitrate_kbps: &[u32] = if is_mpeg1 {
+        &[0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 0]
+    } else {
+        &[0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160, 0]
+    };
+    let sample_rates: [u32; 3] = match version_bits {
+        0x03 => [44100, 48000, 32000], // MPEG1
+        0x02 => [22050, 24000, 16000], // MPEG2
+        0x00 => [11025, 12000, 8000],  // MPEG2.5
+        _ => return None,              // 0x01 is reserved
+    };
+
+    let bitrate = bitrate_kbps[bitrate_index as usize] * 1000;
+    let sample_rate = sample_rates[sample_rate_index as usize];
+    if bitrate == 0 {
+        return None;
+    }
+
+    let samples_per_frame = if is_mpeg1 { 1152 } else { 576 };
+    let frame_length = (samples_per_frame / 8 * bitrate / sample_rate) as usize + padding;
+    if frame_length < 4 {
+        return None;
+    }
+
+    Some(Mp3Frame {
+        sample_rate,
+        samples_per_frame,
+        frame_length,
+    })
+}
+
+/// Return the byte offset past a leading ID3v2 tag, or `0` if `data`
+/// doesn't start with one. The tag size is a 4-byte syncsafe integer (7
+/// usable bits per byte) immediately after the `"ID3"` + version + flags.
+fn skip_id3v2_tag(data: &[u8]) -> usize {
+    if data.len() >= 10 && &data[0..3] == b"ID3" {
+        let size = ((data[6] as u32 & 0x7F) << 21)
+            | ((data[7] as u32 & 0x7F) << 14)
+            | ((data[8] as u32 & 0x7F) << 7)
+            | (data[9] as u32 & 0x7F);
+        10 + size as usize
+    } else {
+        0
+    }
+}
@@ -1,7 +1,7 @@
-use super::client::ElevenLabsVoice;
+use super::client::{Alignment, ElevenLabsVoice};
 use golem_tts::exports::golem::tts::voices::VoiceInfo as WitVoiceInfo;
 use golem_tts::golem::tts::types::{
-    AudioFormat, TtsError as WitTtsError, VoiceGender, VoiceQuality,
+    AudioFormat, TimingInfo as WitTimingInfo, TtsError as WitTtsError, VoiceGender, VoiceQuality,
 };
 
 impl From<ElevenLabsVoice> for WitVoiceInfo {
@@ -21,10 +21,16 @@ impl From<ElevenLabsVoice> for WitVoiceInfo {
             .get("use_case")
             .map_or(false, |u| u.contains("custom"));
 
+        let language = voice
+            .labels
+            .get("language")
+            .map(|l| golem_tts::lang::LanguageIdentifier::parse(l).to_string())
+            .unwrap_or_else(|| "en".to_string());
+
         WitVoiceInfo {
             id: voice.voice_id,
             name: voice.name.clone(),
-            language: "en".to_string(),
+            language,
             additional_languages: vec![],
             gender,
             quality: VoiceQuality::Neural,
@@ -55,6 +61,51 @@ pub fn audio_format_to_elevenlabs(format: AudioFormat) -> &'static str {
     }
 }
 
+/// Group per-character alignment data into word-level timing marks, splitting
+/// on whitespace characters and reporting byte offsets into the original
+/// text so callers can align marks with their own input slices.
+pub fn alignment_to_word_timing_marks(alignment: &Alignment) -> Vec<WitTimingInfo> {
+    let mut marks = Vec::new();
+    let mut byte_offset = 0usize;
+    let mut word_start_offset = 0usize;
+    let mut word_start_time: Option<f32> = None;
+    let mut word = String::new();
+
+    for (i, ch) in alignment.characters.iter().enumerate() {
+        if ch.chars().all(char::is_whitespace) {
+            if !word.is_empty() {
+                marks.push(WitTimingInfo {
+                    time_ms: (word_start_time.unwrap_or(0.0) * 1000.0) as u32,
+                    mark_type: "word".to_string(),
+                    text: std::mem::take(&mut word),
+                    start_offset: Some(word_start_offset as u32),
+                    end_offset: Some(byte_offset as u32),
+                });
+                word_start_time = None;
+            }
+        } else {
+            if word.is_empty() {
+                word_start_offset = byte_offset;
+                word_start_time = alignment.character_start_times_seconds.get(i).copied();
+            }
+            word.push_str(ch);
+        }
+        byte_offset += ch.len();
+    }
+
+    if !word.is_empty() {
+        marks.push(WitTimingInfo {
+            time_ms: (word_start_time.unwrap_or(0.0) * 1000.0) as u32,
+            mark_type: "word".to_string(),
+            text: word,
+            start_offset: Some(word_start_offset as u32),
+            end_offset: Some(byte_offset as u32),
+        });
+    }
+
+    marks
+}
+
 /// Parse ElevenLabs error response
 pub fn parse_elevenlabs_error(status: u16, body: &str) -> WitTtsError {
     // Try to parse JSON error response
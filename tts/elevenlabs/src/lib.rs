@@ -1,11 +1,25 @@
 // ElevenLabs TTS provider
+//
+// Following the tts-rs pattern of gating whole backends behind cargo
+// features, streaming is behind its own feature (on by default) so a
+// component that only needs `synthesize` can build with
+// `--no-default-features` and drop the streaming worker thread entirely:
+//   - "streaming": the HTTP chunked-streaming `StreamingGuest` impl
+mod chunking;
 mod client;
 mod conversions;
+#[cfg(feature = "streaming")]
+mod segmentation;
+#[cfg(feature = "streaming")]
+mod streaming;
+mod voice_cloning;
 
 use client::{ElevenLabsClient, Voice};
 use conversions::*;
+use golem_tts::config::get_endpoint_config;
+use golem_tts::config::validate_config_key;
 use golem_tts::durability::{DurableTts, ExtendedGuest};
-use golem_tts::error::{invalid_text, unsupported, voice_not_found};
+use golem_tts::error::{invalid_text, lexicon_not_found, unsupported, voice_not_found};
 use golem_tts::golem::tts::advanced::{
     AudioSample, Guest as AdvancedGuest, LongFormJob, LongFormResult, PronunciationEntry,
     VoiceDesignParams,
@@ -15,18 +29,62 @@ use golem_tts::golem::tts::synthesis::{
     Guest as SynthesisGuest, SynthesisOptions, ValidationResult,
 };
 use golem_tts::golem::tts::types::{
-    SynthesisResult, TextInput, TimingInfo, TtsError, VoiceGender, VoiceQuality,
+    AudioChunk, SynthesisResult, TextInput, TimingInfo, TtsError, VoiceGender, VoiceQuality,
 };
 use golem_tts::golem::tts::voices::{Guest as VoicesGuest, LanguageInfo, VoiceFilter, VoiceInfo};
+use golem_tts::guest::AudioQueryGuest;
+use golem_tts::guest::VocabularyFilterGuest;
+use golem_tts::guest::DictionaryGuest;
+use golem_tts::lexicon::{Lexicon, LexiconEntry};
+use golem_tts::long_form::LongFormTracker;
 use log::{debug, info, trace};
+use std::cell::RefCell;
+use std::collections::HashMap;
+#[cfg(feature = "streaming")]
+use streaming::StreamManager;
+use voice_cloning::VoiceCloner;
 
 struct ElevenLabsComponent;
 
+thread_local! {
+    #[cfg(feature = "streaming")]
+    static STREAM_MANAGER: RefCell<Option<StreamManager>> = RefCell::new(None);
+    static LEXICONS: RefCell<HashMap<String, Lexicon>> = RefCell::new(HashMap::new());
+    static LONG_FORM: LongFormTracker = LongFormTracker::new();
+}
+
 impl ElevenLabsComponent {
     fn create_client() -> Result<ElevenLabsClient, TtsError> {
         ElevenLabsClient::new()
     }
 
+    fn create_voice_cloner() -> Result<VoiceCloner, TtsError> {
+        let api_key = validate_config_key("ELEVENLABS_API_KEY")?;
+        let base_url = get_endpoint_config("https://api.elevenlabs.io");
+        Ok(VoiceCloner::new(api_key, base_url))
+    }
+
+    #[cfg(feature = "streaming")]
+    fn with_stream_manager<R>(f: impl FnOnce(&StreamManager) -> Result<R, TtsError>) -> Result<R, TtsError> {
+        STREAM_MANAGER.with(|cell| {
+            if cell.borrow().is_none() {
+                *cell.borrow_mut() = Some(StreamManager::new()?);
+            }
+            f(cell.borrow().as_ref().unwrap())
+        })
+    }
+
+    /// Rewrite `text` using every lexicon registered so far. Lexicons are
+    /// applied in `HashMap` iteration order, so the order between lexicons
+    /// is not defined. ElevenLabs has no SSML input, so phonetic entries
+    /// fall back to their plain-text alias.
+    fn apply_lexicons(text: &str) -> String {
+        LEXICONS.with(|cell| {
+            let lexicons: Vec<Lexicon> = cell.borrow().values().cloned().collect();
+            golem_tts::lexicon::apply_all(&lexicons, text, false)
+        })
+    }
+
     fn voice_to_info(voice: &Voice) -> VoiceInfo {
         VoiceInfo {
             id: voice.voice_id.clone(),
@@ -51,11 +109,19 @@ impl ElevenLabsComponent {
 }
 
 impl VoicesGuest for ElevenLabsComponent {
-    fn list_voices(_filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
+    fn list_voices(filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("ElevenLabs: Listing voices");
         let client = Self::create_client()?;
-        let voices = client.list_voices()?;
-        Ok(voices.iter().map(|v| Self::voice_to_info(v)).collect())
+        let voices: Vec<VoiceInfo> = client
+            .list_voices()?
+            .iter()
+            .map(|v| Self::voice_to_info(v))
+            .collect();
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            None,
+            filter.as_ref(),
+        ))
     }
 
     fn get_voice(voice_id: String) -> Result<VoiceInfo, TtsError> {
@@ -71,17 +137,20 @@ impl VoicesGuest for ElevenLabsComponent {
 
     fn search_voices(
         query: String,
-        _filter: Option<VoiceFilter>,
+        filter: Option<VoiceFilter>,
     ) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("ElevenLabs: Searching voices: {}", query);
         let client = Self::create_client()?;
-        let voices = client.list_voices()?;
-        let query_lower = query.to_lowercase();
-        Ok(voices
+        let voices: Vec<VoiceInfo> = client
+            .list_voices()?
             .iter()
-            .filter(|v| v.name.to_lowercase().contains(&query_lower))
             .map(|v| Self::voice_to_info(v))
-            .collect())
+            .collect();
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            Some(&query),
+            filter.as_ref(),
+        ))
     }
 
     fn list_languages() -> Result<Vec<LanguageInfo>, TtsError> {
@@ -106,7 +175,13 @@ impl SynthesisGuest for ElevenLabsComponent {
         }
 
         let client = Self::create_client()?;
-        let response = client.text_to_speech(&input.content, &options.voice_id)?;
+        let content = Self::apply_lexicons(&input.content);
+        let response = chunking::synthesize_long(
+            &client,
+            &content,
+            &options.voice_id,
+            chunking::DEFAULT_MAX_CHUNK_CHARS,
+        )?;
 
         Ok(SynthesisResult {
             audio_data: response.audio_data,
@@ -125,8 +200,12 @@ impl SynthesisGuest for ElevenLabsComponent {
             .collect()
     }
 
-    fn get_timing_marks(_input: TextInput, _voice_id: String) -> Result<Vec<TimingInfo>, TtsError> {
-        Err(unsupported("ElevenLabs does not support timing marks"))
+    fn get_timing_marks(input: TextInput, voice_id: String) -> Result<Vec<TimingInfo>, TtsError> {
+        info!("ElevenLabs: Getting timing marks for {} chars", input.content.len());
+        let client = Self::create_client()?;
+        let content = Self::apply_lexicons(&input.content);
+        let response = client.text_to_speech_with_timestamps(&content, &voice_id)?;
+        Ok(alignment_to_word_timing_marks(&response.alignment))
     }
 
     fn validate_input(input: TextInput, _voice_id: String) -> Result<ValidationResult, TtsError> {
@@ -151,45 +230,81 @@ impl SynthesisGuest for ElevenLabsComponent {
     }
 }
 
+#[cfg(feature = "streaming")]
+impl StreamingGuest for ElevenLabsComponent {
+    fn create_stream(options: SynthesisOptions) -> Result<StreamSession, TtsError> {
+        info!("ElevenLabs: Creating streaming session for voice {}", options.voice_id);
+        Self::with_stream_manager(|manager| manager.create_stream(options))
+    }
+
+    fn stream_send_text(session_id: String, input: TextInput) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.send_text(session_id, input))
+    }
+
+    fn stream_finish(session_id: String) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.finish(session_id))
+    }
+
+    fn stream_receive_chunk(session_id: String) -> Result<Option<AudioChunk>, TtsError> {
+        Self::with_stream_manager(|manager| manager.receive_chunk(session_id))
+    }
+
+    fn stream_has_pending(session_id: String) -> Result<bool, TtsError> {
+        Self::with_stream_manager(|manager| manager.has_pending(session_id))
+    }
+
+    fn stream_get_status(session_id: String) -> Result<StreamStatus, TtsError> {
+        Self::with_stream_manager(|manager| manager.get_status(session_id))
+    }
+
+    fn stream_close(session_id: String) -> Result<(), TtsError> {
+        Self::with_stream_manager(|manager| manager.close(session_id))
+    }
+}
+
+/// Built without the "streaming" feature: the `Guest` trait still needs an
+/// impl, but every method reports the capability as absent rather than
+/// linking the worker-thread transport in [`streaming`].
+#[cfg(not(feature = "streaming"))]
 impl StreamingGuest for ElevenLabsComponent {
     fn create_stream(_options: SynthesisOptions) -> Result<StreamSession, TtsError> {
-        Err(unsupported("ElevenLabs streaming not yet implemented"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
     fn stream_send_text(_session_id: String, _input: TextInput) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
     fn stream_finish(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
-    fn stream_receive_chunk(_session_id: String) -> Result<Option<Vec<u8>>, TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_receive_chunk(_session_id: String) -> Result<Option<AudioChunk>, TtsError> {
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
     fn stream_has_pending(_session_id: String) -> Result<bool, TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
     fn stream_get_status(_session_id: String) -> Result<StreamStatus, TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 
     fn stream_close(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+        Err(unsupported("ElevenLabs built without the \"streaming\" feature"))
     }
 }
 
 impl AdvancedGuest for ElevenLabsComponent {
     fn create_voice_clone(
-        _name: String,
-        _audio_samples: Vec<AudioSample>,
-        _description: Option<String>,
+        name: String,
+        audio_samples: Vec<AudioSample>,
+        description: Option<String>,
     ) -> Result<String, TtsError> {
-        Err(unsupported(
-            "Voice cloning requires multipart upload - not supported in WASI",
-        ))
+        info!("ElevenLabs: Cloning voice '{}' from {} samples", name, audio_samples.len());
+        let cloner = Self::create_voice_cloner()?;
+        cloner.create_voice_clone(name, audio_samples, description)
     }
 
     fn design_voice(
@@ -218,43 +333,83 @@ impl AdvancedGuest for ElevenLabsComponent {
     }
 
     fn create_lexicon(
-        _name: String,
-        _language: String,
-        _entries: Option<Vec<PronunciationEntry>>,
+        name: String,
+        language: String,
+        entries: Option<Vec<PronunciationEntry>>,
     ) -> Result<String, TtsError> {
-        Err(unsupported("ElevenLabs does not support lexicons"))
+        debug!("ElevenLabs: Creating lexicon '{}' ({})", name, language);
+        let entries = entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(LexiconEntry::from)
+            .collect();
+        let lexicon = Lexicon::new(name, language, entries);
+        let lexicon_id = lexicon.id.clone();
+        LEXICONS.with(|cell| cell.borrow_mut().insert(lexicon_id.clone(), lexicon));
+        Ok(lexicon_id)
     }
 
-    fn add_lexicon_entry(_lexicon_id: String, _entry: PronunciationEntry) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    fn add_lexicon_entry(lexicon_id: String, entry: PronunciationEntry) -> Result<(), TtsError> {
+        LEXICONS.with(|cell| {
+            let mut lexicons = cell.borrow_mut();
+            let lexicon = lexicons
+                .get_mut(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            lexicon.add_entry(entry.into());
+            Ok(())
+        })
     }
 
-    fn remove_lexicon_entry(_lexicon_id: String, _word: String) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    fn remove_lexicon_entry(lexicon_id: String, word: String) -> Result<(), TtsError> {
+        LEXICONS.with(|cell| {
+            let mut lexicons = cell.borrow_mut();
+            let lexicon = lexicons
+                .get_mut(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            lexicon.remove_entry(&word);
+            Ok(())
+        })
     }
 
-    fn export_lexicon(_lexicon_id: String) -> Result<String, TtsError> {
-        Err(unsupported("Lexicon not supported"))
+    fn export_lexicon(lexicon_id: String) -> Result<String, TtsError> {
+        LEXICONS.with(|cell| {
+            let lexicons = cell.borrow();
+            let lexicon = lexicons
+                .get(&lexicon_id)
+                .ok_or_else(|| lexicon_not_found(lexicon_id.clone()))?;
+            Ok(lexicon.export_pls())
+        })
     }
 
     fn synthesize_long_form(
-        _content: String,
-        _voice_id: String,
-        _output_location: String,
-        _chapter_breaks: Option<Vec<u32>>,
+        content: String,
+        voice_id: String,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
     ) -> Result<LongFormJob, TtsError> {
-        Err(unsupported("Long-form synthesis not yet implemented"))
+        info!("ElevenLabs: Starting long-form synthesis of {} chars", content.len());
+        let client = Self::create_client()?;
+        LONG_FORM.with(|tracker| {
+            tracker.start(&content, output_location, chapter_breaks, 5000, |segment| {
+                client.text_to_speech(segment, &voice_id).map(|r| r.audio_data)
+            })
+        })
     }
 
-    fn get_long_form_status(_job_id: String) -> Result<LongFormResult, TtsError> {
-        Err(unsupported("Long-form not supported"))
+    fn get_long_form_status(job_id: String) -> Result<LongFormResult, TtsError> {
+        LONG_FORM.with(|tracker| tracker.get_long_form_status(&job_id))
     }
 
-    fn cancel_long_form(_job_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Long-form not supported"))
+    fn cancel_long_form(job_id: String) -> Result<(), TtsError> {
+        LONG_FORM.with(|tracker| tracker.cancel_long_form(&job_id))
     }
 }
 
+impl AudioQueryGuest for ElevenLabsComponent {}
+
+impl VocabularyFilterGuest for ElevenLabsComponent {}
+impl DictionaryGuest for ElevenLabsComponent {}
+
 impl ExtendedGuest for ElevenLabsComponent {}
 
 type DurableElevenLabsComponent = DurableTts<ElevenLabsComponent>;
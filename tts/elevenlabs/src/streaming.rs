@@ -0,0 +1,289 @@
+//! HTTP chunked-streaming implementation for ElevenLabs TTS.
+//!
+//! WASI 0.23 has no WebSocket support, so this does not open a socket to
+//! ElevenLabs' `stream-input` endpoint. Instead `send_text` runs incoming
+//! text through a [`SegmentBuffer`] that stabilizes it into sentence- (or
+//! clause-) sized segments, and each stabilized segment is handed to a
+//! per-session worker thread that POSTs it through
+//! [`ElevenLabsClient::text_to_speech_stream`] and drains the chunked HTTP
+//! response incrementally, collecting the segment's audio frames and
+//! pushing them onto a shared `VecDeque` once the segment has been read in
+//! full. Segments are processed one at a time so audio
+//! comes back in the order the text was spoken, and `receive_chunk` /
+//! `has_pending` pop from that queue.
+//!
+//! Each segment is synthesized through [`retry_with_config`], which builds
+//! a fresh request on every attempt rather than trying to resume a broken
+//! one: on a retryable error the segment's audio collected so far is
+//! discarded and the whole segment is resent, so nothing is ever
+//! duplicated or lost. `reconnects`/`last_error` track this for
+//! diagnostics; the streaming WIT interface has no getters for them, so
+//! they're surfaced via the component log rather than `get_status`.
+use crate::client::ElevenLabsClient;
+use crate::segmentation::{Granularity, SegmentBuffer};
+use golem_tts::exports::golem::tts::streaming::{
+    StreamSession as WitStreamSession, StreamStatus as WitStreamStatus,
+};
+use golem_tts::exports::golem::tts::synthesis::SynthesisOptions as WitSynthesisOptions;
+use golem_tts::golem::tts::types::{
+    AudioChunk as WitAudioChunk, TextInput as WitTextInput, TtsError as WitTtsError,
+};
+use golem_tts::retry::{retry_with_config, RetryConfig};
+use log::warn;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_VOICE_ID: &str = "21m00Tcm4TlvDq8ikWAM";
+
+#[derive(Clone, PartialEq)]
+enum StreamStatusInternal {
+    Active,
+    Finished,
+    Error(String),
+}
+
+/// A unit of work handed to the per-session worker thread.
+enum SegmentMsg {
+    Text(String),
+    Finish,
+}
+
+struct StreamSessionData {
+    model: String,
+    encoding: String,
+    sample_rate: u32,
+    segmenter: Mutex<SegmentBuffer>,
+    worker: Sender<SegmentMsg>,
+    inbound: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    status: Arc<Mutex<StreamStatusInternal>>,
+    /// Number of retry attempts made across the session.
+    reconnects: Arc<Mutex<u32>>,
+    /// The most recent error seen on any attempt, retryable or not. Kept
+    /// even after a retry recovers, as a breadcrumb for why a reconnect
+    /// happened.
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+pub struct StreamManager {
+    sessions: Arc<Mutex<HashMap<String, StreamSessionData>>>,
+    client: ElevenLabsClient,
+}
+
+impl StreamManager {
+    pub fn new() -> Result<Self, WitTtsError> {
+        Ok(Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            client: ElevenLabsClient::new()?,
+        })
+    }
+
+    pub fn create_stream(
+        &self,
+        options: WitSynthesisOptions,
+    ) -> Result<WitStreamSession, WitTtsError> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let voice_id = if options.voice_id.is_empty() {
+            DEFAULT_VOICE_ID.to_string()
+        } else {
+            options.voice_id.clone()
+        };
+        let encoding = "mp3".to_string();
+        let sample_rate = options
+            .audio_config
+            .as_ref()
+            .and_then(|c| c.sample_rate)
+            .unwrap_or(44100);
+
+        let inbound = Arc::new(Mutex::new(VecDeque::new()));
+        let status = Arc::new(Mutex::new(StreamStatusInternal::Active));
+        let reconnects = Arc::new(Mutex::new(0u32));
+        let last_error = Arc::new(Mutex::new(None));
+        let (tx, rx) = mpsc::channel::<SegmentMsg>();
+
+        let client = self.client.clone();
+        let worker_voice_id = voice_id.clone();
+        let worker_inbound = inbound.clone();
+        let worker_status = status.clone();
+        let worker_reconnects = reconnects.clone();
+        let worker_last_error = last_error.clone();
+
+        std::thread::spawn(move || {
+            for msg in rx {
+                let text = match msg {
+                    SegmentMsg::Text(text) => text,
+                    SegmentMsg::Finish => break,
+                };
+
+                let mut attempt = 0u32;
+                let outcome = retry_with_config(&RetryConfig::default(), || {
+                    if attempt > 0 {
+                        *worker_reconnects.lock().unwrap() += 1;
+                        warn!("ElevenLabs stream reconnect attempt {} for segment", attempt);
+                    }
+                    attempt += 1;
+
+                    // Collect into a local buffer rather than pushing onto
+                    // `inbound` as events arrive: if this attempt fails
+                    // partway through, the whole segment is resent from
+                    // scratch, so any chunks already read here must be
+                    // discarded instead of left queued for playback.
+                    let record_error = |e: WitTtsError| {
+                        *worker_last_error.lock().unwrap() = Some(format!("{:?}", e));
+                        e
+                    };
+
+                    let mut chunks = Vec::new();
+                    let events = client
+                        .text_to_speech_stream(&text, &worker_voice_id)
+                        .map_err(record_error)?;
+                    for event in events {
+                        chunks.push(event.map_err(record_error)?.0);
+                    }
+                    Ok(chunks)
+                });
+
+                match outcome {
+                    Ok(chunks) => {
+                        worker_inbound.lock().unwrap().extend(chunks);
+                    }
+                    Err(e) => {
+                        *worker_status.lock().unwrap() =
+                            StreamStatusInternal::Error(format!("{:?}", e));
+                        return;
+                    }
+                }
+            }
+            let reconnects = *worker_reconnects.lock().unwrap();
+            if reconnects > 0 {
+                warn!(
+                    "ElevenLabs stream finished after {} reconnect(s); last error: {:?}",
+                    reconnects,
+                    *worker_last_error.lock().unwrap()
+                );
+            }
+            *worker_status.lock().unwrap() = StreamStatusInternal::Finished;
+        });
+
+        let session_data = StreamSessionData {
+            model: "eleven_monolingual_v1".to_string(),
+            encoding: encoding.clone(),
+            sample_rate,
+            segmenter: Mutex::new(SegmentBuffer::new(Granularity::from_config())),
+            worker: tx,
+            inbound,
+            status,
+            reconnects,
+            last_error,
+        };
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(session_id.clone(), session_data);
+
+        Ok(WitStreamSession {
+            session_id,
+            model: "eleven_monolingual_v1".to_string(),
+            encoding,
+            sample_rate,
+        })
+    }
+
+    /// Feed `input` through the session's [`SegmentBuffer`] and hand off
+    /// any segment that just stabilized to the worker thread.
+    pub fn send_text(&self, session_id: String, input: WitTextInput) -> Result<(), WitTtsError> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.clone()))?;
+
+        let segments = session.segmenter.lock().unwrap().push(&input.content);
+        for segment in segments {
+            session
+                .worker
+                .send(SegmentMsg::Text(segment))
+                .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(&self, session_id: String) -> Result<(), WitTtsError> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.clone()))?;
+
+        if let Some(tail) = session.segmenter.lock().unwrap().flush_remaining() {
+            session
+                .worker
+                .send(SegmentMsg::Text(tail))
+                .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))?;
+        }
+
+        session
+            .worker
+            .send(SegmentMsg::Finish)
+            .map_err(|_| WitTtsError::InternalError("Stream already closed".to_string()))
+    }
+
+    pub fn receive_chunk(&self, session_id: String) -> Result<Option<WitAudioChunk>, WitTtsError> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
+
+        Ok(session
+            .inbound
+            .lock()
+            .unwrap()
+            .pop_front()
+            .map(|data| WitAudioChunk {
+                data,
+                is_final: false,
+                sequence_number: 0,
+                timing_info: None,
+            }))
+    }
+
+    pub fn has_pending(&self, session_id: String) -> Result<bool, WitTtsError> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
+
+        let has_buffered = !session.inbound.lock().unwrap().is_empty();
+        let still_active = matches!(*session.status.lock().unwrap(), StreamStatusInternal::Active);
+        Ok(has_buffered || still_active)
+    }
+
+    /// `reconnects` and `last_error` aren't part of this WIT struct's fixed
+    /// shape, so they stay internal diagnostics (see the module doc); only
+    /// a terminal, retries-exhausted error is reflected here.
+    pub fn get_status(&self, session_id: String) -> Result<WitStreamStatus, WitTtsError> {
+        let sessions = self.sessions.lock().unwrap();
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id))?;
+
+        let status = session.status.lock().unwrap().clone();
+        let has_pending = !session.inbound.lock().unwrap().is_empty();
+
+        let (status_str, error) = match &status {
+            StreamStatusInternal::Active => ("active", None),
+            StreamStatusInternal::Finished => ("finished", None),
+            StreamStatusInternal::Error(msg) => ("error", Some(msg.clone())),
+        };
+
+        Ok(WitStreamStatus {
+            status: status_str.to_string(),
+            is_active: status == StreamStatusInternal::Active,
+            has_pending_chunks: has_pending,
+            error,
+        })
+    }
+
+    pub fn close(&self, session_id: String) -> Result<(), WitTtsError> {
+        self.sessions.lock().unwrap().remove(&session_id);
+        Ok(())
+    }
+}
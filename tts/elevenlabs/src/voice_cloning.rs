@@ -5,8 +5,8 @@
 
 use golem_tts::exports::golem::tts::advanced::AudioSample as WitAudioSample;
 use golem_tts::golem::tts::types::TtsError as WitTtsError;
-use golem_tts::http::WstdHttpClient;
-use serde::{Deserialize, Serialize};
+use golem_tts::http::{MultipartForm, WstdHttpClient};
+use serde::Deserialize;
 
 pub struct VoiceCloner {
     api_key: String,
@@ -29,45 +29,34 @@ impl VoiceCloner {
         audio_samples: Vec<WitAudioSample>,
         description: Option<String>,
     ) -> Result<String, WitTtsError> {
-        // Note: Multipart form-data is complex in current HTTP client
-        // This is a reference implementation showing the intended structure
-
-        return Err(WitTtsError::UnsupportedOperation(
-            "Voice cloning with multipart upload requires advanced HTTP client. \
-             Current WASI HTTP implementation has limited multipart support. \
-             Use ElevenLabs web interface for voice cloning."
-                .to_string(),
-        ));
-
-        /* Reference implementation for when multipart is available:
+        validate_audio_samples(&audio_samples)?;
 
         let http = WstdHttpClient::new();
 
-        // Construct multipart form data
-        let mut form = MultipartForm::new();
-        form.add_text("name", &name);
-
+        let mut form = MultipartForm::new().add_text("name", &name);
         if let Some(desc) = description {
-            form.add_text("description", &desc);
+            form = form.add_text("description", desc);
         }
-
-        // Add audio files
         for (idx, sample) in audio_samples.iter().enumerate() {
             let filename = format!("sample_{}.mp3", idx);
-            form.add_file("files", &filename, &sample.audio_data)?;
+            form = form.add_file_with_content_type(
+                "files",
+                filename,
+                "audio/mpeg",
+                sample.audio_data.clone(),
+            );
         }
 
         let url = format!("{}/v1/voices/add", self.base_url);
         let response = http
             .post(&url)
             .header("xi-api-key", &self.api_key)
-            .multipart(form)?
+            .multipart(form)
             .send()?
             .error_for_status()?;
 
         let clone_response: VoiceCloneResponse = response.json()?;
         Ok(clone_response.voice_id)
-        */
     }
 
     pub fn delete_voice_clone(&self, voice_id: String) -> Result<(), WitTtsError> {
@@ -1,23 +1,178 @@
-//! Simple token-based authentication for Google Cloud TTS
-//! Users should provide pre-generated OAuth2 access tokens
-
-use golem_tts::golem::tts::types::TtsError as WitTtsError;
-
-/// Get access token from environment variable
-pub fn get_access_token_from_env() -> Result<String, WitTtsError> {
-    std::env::var("GOOGLE_ACCESS_TOKEN").map_err(|_| {
-        WitTtsError::InternalError(
-            "GOOGLE_ACCESS_TOKEN environment variable not set. \
-             Please set it to a valid OAuth2 access token. \
-             You can generate one using: gcloud auth print-access-token"
-                .to_string(),
-        )
-    })
-}
-
-/// Get project ID from environment variable
-pub fn get_project_id_from_env() -> Result<String, WitTtsError> {
-    std::env::var("GOOGLE_PROJECT_ID").map_err(|_| {
-        WitTtsError::InternalError("GOOGLE_PROJECT_ID environment variable not set".to_string())
-    })
+//! Google Cloud authentication.
+//!
+//! Mints and caches OAuth2 access tokens from a service-account JSON via the
+//! JWT-bearer flow (`GOOGLE_APPLICATION_CREDENTIALS` path or inline
+//! `GOOGLE_SERVICE_ACCOUNT_JSON`), so callers don't have to refresh an
+//! hourly token out-of-band. Falls back to a pre-minted `GOOGLE_ACCESS_TOKEN`
+//! for callers who already manage their own.
+use golem_tts::cache::CacheEntry;
+use golem_tts::config::{get_optional_config, validate_config_key};
+use golem_tts::error::{from_reqwest_error, internal_error, tts_error_from_status};
+use golem_tts::golem::tts::types::TtsError;
+use rsa::pkcs1v15::SigningKey;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::signature::{SignatureEncoding, Signer};
+use rsa::RsaPrivateKey;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::cell::RefCell;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Re-mint a cached token this many seconds before it actually expires, so a
+/// request never races a token going stale mid-flight.
+const EXPIRY_SKEW_SECS: u64 = 60;
+
+thread_local! {
+    static TOKEN_CACHE: RefCell<Option<CacheEntry<String>>> = RefCell::new(None);
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+}
+
+#[derive(Serialize)]
+struct JwtHeader {
+    alg: &'static str,
+    typ: &'static str,
+}
+
+#[derive(Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Get a valid access token, minting and caching one from a configured
+/// service account if present, or falling back to `GOOGLE_ACCESS_TOKEN`.
+pub fn get_access_token() -> Result<String, TtsError> {
+    match load_service_account_key()? {
+        Some(key) => get_or_mint_token(&key),
+        None => validate_config_key("GOOGLE_ACCESS_TOKEN"),
+    }
+}
+
+fn get_or_mint_token(key: &ServiceAccountKey) -> Result<String, TtsError> {
+    let cached = TOKEN_CACHE.with(|cell| cell.borrow().clone());
+    if let Some(entry) = cached {
+        if !is_expired_with_skew(&entry) {
+            return Ok(entry.data);
+        }
+    }
+
+    let assertion = build_assertion(key)?;
+    let (access_token, expires_in) = exchange_assertion(&assertion)?;
+    let entry = CacheEntry::new(access_token.clone(), Duration::from_secs(expires_in));
+    TOKEN_CACHE.with(|cell| *cell.borrow_mut() = Some(entry));
+
+    Ok(access_token)
+}
+
+/// Like [`CacheEntry::is_expired`], but re-mints `EXPIRY_SKEW_SECS` early so a
+/// request never races a token going stale mid-flight.
+fn is_expired_with_skew(entry: &CacheEntry<String>) -> bool {
+    SystemTime::now()
+        .duration_since(entry.cached_at)
+        .map(|elapsed| elapsed + Duration::from_secs(EXPIRY_SKEW_SECS) > entry.ttl)
+        .unwrap_or(true)
+}
+
+/// Build and RSA-SHA256-sign a one-hour JWT-bearer assertion for `key`.
+fn build_assertion(key: &ServiceAccountKey) -> Result<String, TtsError> {
+    let header = base64url(
+        &serde_json::to_vec(&JwtHeader {
+            alg: "RS256",
+            typ: "JWT",
+        })
+        .map_err(|e| internal_error(format!("Encoding JWT header: {}", e)))?,
+    );
+
+    let iat = now_unix();
+    let claims = base64url(
+        &serde_json::to_vec(&JwtClaims {
+            iss: &key.client_email,
+            scope: CLOUD_PLATFORM_SCOPE,
+            aud: TOKEN_URL,
+            iat,
+            exp: iat + 3600,
+        })
+        .map_err(|e| internal_error(format!("Encoding JWT claims: {}", e)))?,
+    );
+
+    let signing_input = format!("{}.{}", header, claims);
+
+    let private_key = RsaPrivateKey::from_pkcs8_pem(&key.private_key)
+        .map_err(|e| internal_error(format!("Parsing service account private key: {}", e)))?;
+    let signing_key = SigningKey::<Sha256>::new(private_key);
+    let signature = signing_key
+        .try_sign(signing_input.as_bytes())
+        .map_err(|e| internal_error(format!("Signing JWT assertion: {}", e)))?;
+
+    Ok(format!("{}.{}", signing_input, base64url(&signature.to_bytes())))
+}
+
+/// Exchange a signed JWT assertion for an access token via the OAuth2
+/// JWT-bearer grant.
+fn exchange_assertion(assertion: &str) -> Result<(String, u64), TtsError> {
+    let response = reqwest::Client::new()
+        .post(TOKEN_URL)
+        .form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion),
+        ])
+        .send()
+        .map_err(|e| from_reqwest_error("Google OAuth2 token exchange", e))?;
+
+    if !response.status().is_success() {
+        return Err(tts_error_from_status(response.status()));
+    }
+
+    let token: TokenResponse = response
+        .json()
+        .map_err(|e| from_reqwest_error("Parsing Google OAuth2 token response", e))?;
+
+    Ok((token.access_token, token.expires_in))
+}
+
+fn load_service_account_key() -> Result<Option<ServiceAccountKey>, TtsError> {
+    if let Some(inline) = get_optional_config("GOOGLE_SERVICE_ACCOUNT_JSON") {
+        return parse_service_account_key(&inline).map(Some);
+    }
+
+    if let Some(path) = get_optional_config("GOOGLE_APPLICATION_CREDENTIALS") {
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| internal_error(format!("Reading {}: {}", path, e)))?;
+        return parse_service_account_key(&contents).map(Some);
+    }
+
+    Ok(None)
+}
+
+fn parse_service_account_key(json: &str) -> Result<ServiceAccountKey, TtsError> {
+    serde_json::from_str(json)
+        .map_err(|e| internal_error(format!("Parsing service account JSON: {}", e)))
+}
+
+fn base64url(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
@@ -0,0 +1,51 @@
+// Long-text chunking for Google Cloud TTS, whose `text:synthesize` endpoint
+// caps the request body around 5000 bytes of input text.
+
+/// Default fragment size in characters, equal to Google's 5000-byte limit;
+/// fragments with multi-byte characters can exceed that byte limit.
+pub const DEFAULT_MAX_CHUNK_CHARS: usize = 5000;
+
+/// Split `text` into fragments no larger than `max_chars`, breaking on
+/// whitespace where possible so words are never split across a boundary.
+///
+/// Whitespace is first canonicalized (trimmed, runs collapsed to a single
+/// space) so fragment boundaries are deterministic regardless of the
+/// caller's formatting.
+pub fn split_text_into_fragments(text: &str, max_chars: usize) -> Vec<String> {
+    let canonical = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if canonical.is_empty() {
+        return vec![];
+    }
+
+    if canonical.chars().count() <= max_chars {
+        return vec![canonical];
+    }
+
+    let mut fragments = Vec::new();
+    let mut remaining: Vec<char> = canonical.chars().collect();
+
+    while remaining.len() > max_chars {
+        let window: Vec<char> = remaining[..max_chars + 1].to_vec();
+        let break_at = window
+            .iter()
+            .rposition(|c| *c == ' ')
+            .unwrap_or(max_chars);
+
+        let fragment: String = window[..break_at].iter().collect();
+        fragments.push(fragment);
+
+        let skip = if break_at < window.len() && window[break_at] == ' ' {
+            break_at + 1
+        } else {
+            break_at
+        };
+        remaining = remaining[skip..].to_vec();
+    }
+
+    if !remaining.is_empty() {
+        fragments.push(remaining.into_iter().collect());
+    }
+
+    fragments
+}
@@ -1,7 +1,5 @@
 // Google Cloud TTS client with service account authentication
-use golem_tts::config::{
-    get_config_with_default, get_max_retries_config, get_timeout_config, validate_config_key,
-};
+use golem_tts::config::{get_config_with_default, get_max_retries_config, get_timeout_config};
 use golem_tts::error::{from_reqwest_error, internal_error, tts_error_from_status};
 use golem_tts::golem::tts::types::TtsError;
 use log::trace;
@@ -9,6 +7,27 @@ use reqwest::{Client, Method};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Plain-text vs. SSML synthesis input, mirroring Google's `text:synthesize`
+/// request body, which accepts exactly one of `input.text` or `input.ssml`.
+#[derive(Debug, Clone, Serialize)]
+pub enum SynthesisInput {
+    #[serde(rename = "text")]
+    Text(String),
+    #[serde(rename = "ssml")]
+    Ssml(String),
+}
+
+/// Optional prosody and output tuning for a synthesis request, mapped onto
+/// Google's `audioConfig` fields. Unset fields are omitted from the request
+/// so Google falls back to its own defaults.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioTuning {
+    pub speaking_rate: Option<f32>,
+    pub pitch: Option<f32>,
+    pub volume_gain_db: Option<f32>,
+    pub sample_rate_hertz: Option<u32>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub max_retries: u32,
@@ -38,8 +57,9 @@ pub struct GoogleTtsClient {
 
 impl GoogleTtsClient {
     pub fn new() -> Result<Self, TtsError> {
-        // Try environment variables first (simpler for WASI)
-        let access_token = validate_config_key("GOOGLE_ACCESS_TOKEN")?;
+        // Mints from a service account when configured, otherwise falls
+        // back to a pre-minted GOOGLE_ACCESS_TOKEN.
+        let access_token = crate::auth::get_access_token()?;
         let project_id = get_config_with_default("GOOGLE_PROJECT_ID", "default-project");
 
         let client = Client::builder()
@@ -57,9 +77,11 @@ impl GoogleTtsClient {
 
     pub fn synthesize_speech(
         &self,
-        text: &str,
+        input: SynthesisInput,
         voice_name: &str,
         language_code: &str,
+        audio_encoding: &str,
+        tuning: &AudioTuning,
     ) -> Result<Vec<u8>, TtsError> {
         let url = "https://texttospeech.googleapis.com/v1/text:synthesize";
 
@@ -74,31 +96,36 @@ impl GoogleTtsClient {
         struct AudioConfig {
             #[serde(rename = "audioEncoding")]
             audio_encoding: String,
-        }
-
-        #[derive(Serialize)]
-        struct Input {
-            text: String,
+            #[serde(rename = "speakingRate", skip_serializing_if = "Option::is_none")]
+            speaking_rate: Option<f32>,
+            #[serde(rename = "pitch", skip_serializing_if = "Option::is_none")]
+            pitch: Option<f32>,
+            #[serde(rename = "volumeGainDb", skip_serializing_if = "Option::is_none")]
+            volume_gain_db: Option<f32>,
+            #[serde(rename = "sampleRateHertz", skip_serializing_if = "Option::is_none")]
+            sample_rate_hertz: Option<u32>,
         }
 
         #[derive(Serialize)]
         struct Request {
-            input: Input,
+            input: SynthesisInput,
             voice: Voice,
             #[serde(rename = "audioConfig")]
             audio_config: AudioConfig,
         }
 
         let body = Request {
-            input: Input {
-                text: text.to_string(),
-            },
+            input,
             voice: Voice {
                 language_code: language_code.to_string(),
                 name: voice_name.to_string(),
             },
             audio_config: AudioConfig {
-                audio_encoding: "MP3".to_string(),
+                audio_encoding: audio_encoding.to_string(),
+                speaking_rate: tuning.speaking_rate,
+                pitch: tuning.pitch,
+                volume_gain_db: tuning.volume_gain_db,
+                sample_rate_hertz: tuning.sample_rate_hertz,
             },
         };
 
@@ -129,41 +156,84 @@ impl GoogleTtsClient {
             .map_err(|e| internal_error(format!("Base64 decode error: {}", e)))
     }
 
-    pub fn list_voices() -> Vec<GoogleVoice> {
-        // Hardcoded list of popular Google voices
-        vec![
-            GoogleVoice {
-                name: "en-US-Neural2-A".to_string(),
-                display_name: "Neural2 A".to_string(),
-                language_code: "en-US".to_string(),
-                gender: "Female".to_string(),
-            },
-            GoogleVoice {
-                name: "en-US-Neural2-C".to_string(),
-                display_name: "Neural2 C".to_string(),
-                language_code: "en-US".to_string(),
-                gender: "Female".to_string(),
-            },
-            GoogleVoice {
-                name: "en-US-Neural2-D".to_string(),
-                display_name: "Neural2 D".to_string(),
-                language_code: "en-US".to_string(),
-                gender: "Male".to_string(),
-            },
-            GoogleVoice {
-                name: "en-US-Wavenet-A".to_string(),
-                display_name: "Wavenet A".to_string(),
-                language_code: "en-US".to_string(),
-                gender: "Male".to_string(),
-            },
-        ]
+    /// Synthesize `text` as one or more fragments of at most
+    /// `max_chunk_chars` characters each, synthesizing sequentially and
+    /// concatenating the raw audio byte streams into a single response.
+    ///
+    /// Only plain text is chunked this way: splitting arbitrary SSML on
+    /// whitespace would cut markup in half, so SSML input is always sent as
+    /// a single request via [`Self::synthesize_speech`].
+    pub fn synthesize_long(
+        &self,
+        text: &str,
+        voice_name: &str,
+        language_code: &str,
+        max_chunk_chars: usize,
+        audio_encoding: &str,
+        tuning: &AudioTuning,
+    ) -> Result<Vec<u8>, TtsError> {
+        let fragments = crate::chunking::split_text_into_fragments(text, max_chunk_chars);
+        if fragments.is_empty() {
+            return Err(TtsError::InvalidText("Text cannot be empty".to_string()));
+        }
+
+        let mut merged_audio = Vec::new();
+        for fragment in fragments {
+            let audio = self.synthesize_speech(
+                SynthesisInput::Text(fragment),
+                voice_name,
+                language_code,
+                audio_encoding,
+                tuning,
+            )?;
+            merged_audio.extend_from_slice(&audio);
+        }
+
+        Ok(merged_audio)
+    }
+
+    /// List available voices via `GET /v1/voices`, optionally scoped to a
+    /// BCP-47 `language_code`. The tag is forwarded as-is: Google's API
+    /// itself widens it (e.g. `"zh"` also surfaces `cmn-*` voices, `"no"`
+    /// surfaces `nb-*` voices), so no local expansion is needed here.
+    pub fn list_voices(&self, language_code: Option<&str>) -> Result<Vec<GoogleVoice>, TtsError> {
+        let mut url = "https://texttospeech.googleapis.com/v1/voices".to_string();
+        if let Some(code) = language_code {
+            url.push_str(&format!("?languageCode={}", code));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .send()
+            .map_err(|e| from_reqwest_error("Google TTS list_voices", e))?;
+
+        if !response.status().is_success() {
+            return Err(tts_error_from_status(response.status()));
+        }
+
+        #[derive(Deserialize)]
+        struct VoicesResponse {
+            #[serde(default)]
+            voices: Vec<GoogleVoice>,
+        }
+
+        let voices_response: VoicesResponse = response
+            .json()
+            .map_err(|e| from_reqwest_error("Parsing Google voices response", e))?;
+
+        Ok(voices_response.voices)
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoogleVoice {
     pub name: String,
-    pub display_name: String,
-    pub language_code: String,
-    pub gender: String,
+    #[serde(rename = "languageCodes", default)]
+    pub language_codes: Vec<String>,
+    #[serde(rename = "ssmlGender", default)]
+    pub ssml_gender: String,
+    #[serde(rename = "naturalSampleRateHertz", default)]
+    pub natural_sample_rate_hertz: u32,
 }
@@ -1,9 +1,13 @@
 // Google Cloud TTS provider
+mod auth;
+mod chunking;
 mod client;
 mod conversions;
 
 use client::{GoogleTtsClient, GoogleVoice};
 use conversions::*;
+use golem_tts::cache::VoiceCache;
+use golem_tts::config::parse_config_u32;
 use golem_tts::durability::{DurableTts, ExtendedGuest};
 use golem_tts::error::{invalid_text, unsupported, voice_not_found};
 use golem_tts::golem::tts::advanced::{
@@ -15,29 +19,81 @@ use golem_tts::golem::tts::synthesis::{
     Guest as SynthesisGuest, SynthesisOptions, ValidationResult,
 };
 use golem_tts::golem::tts::types::{
-    SynthesisResult, TextInput, TimingInfo, TtsError, VoiceQuality,
+    AudioFormat, SynthesisResult, TextInput, TextType, TimingInfo, TtsError, VoiceQuality,
 };
 use golem_tts::golem::tts::voices::{Guest as VoicesGuest, LanguageInfo, VoiceFilter, VoiceInfo};
+use golem_tts::guest::AudioQueryGuest;
+use golem_tts::guest::VocabularyFilterGuest;
+use golem_tts::guest::DictionaryGuest;
 use log::{debug, info, trace};
 
 struct GoogleComponent;
 
+thread_local! {
+    /// Cached full voice catalog (unfiltered), refreshed every
+    /// `TTS_VOICE_CACHE_TTL` seconds (default 300s) so `get_voice` and
+    /// `search_voices` don't each re-hit Google's `voices:list` endpoint.
+    static VOICE_CACHE: VoiceCache<Vec<VoiceInfo>> =
+        VoiceCache::new(parse_config_u32("TTS_VOICE_CACHE_TTL", 300) as u64);
+}
+
 impl GoogleComponent {
     fn create_client() -> Result<GoogleTtsClient, TtsError> {
         GoogleTtsClient::new()
     }
 
+    /// The full, unfiltered voice catalog, served from cache when valid.
+    fn cached_voices() -> Result<Vec<VoiceInfo>, TtsError> {
+        if let Some(voices) = VOICE_CACHE.with(|cache| cache.get()) {
+            return Ok(voices);
+        }
+
+        let client = Self::create_client()?;
+        let voices: Vec<VoiceInfo> = client
+            .list_voices(None)?
+            .iter()
+            .map(Self::voice_to_info)
+            .collect();
+        VOICE_CACHE.with(|cache| cache.set(voices.clone()));
+        Ok(voices)
+    }
+
+    /// Look up `voice_id` in the cached catalog and return its first
+    /// `language_codes` entry (the same value `voice_to_info` exposes as
+    /// `VoiceInfo::language`), so a request is synthesized with the
+    /// `language_code` its own voice was actually published under. Google
+    /// rejects requests where `voice.name` and `language_code` disagree, so
+    /// hardcoding one language here would break every other one. Falls back
+    /// to `"en-US"` if the catalog doesn't recognize the voice; the
+    /// synthesis call itself will then surface Google's own error.
+    fn language_code_for_voice(voice_id: &str) -> Result<String, TtsError> {
+        Ok(Self::cached_voices()?
+            .into_iter()
+            .find(|v| v.id == voice_id)
+            .map(|v| v.language)
+            .unwrap_or_else(|| "en-US".to_string()))
+    }
+
     fn voice_to_info(voice: &GoogleVoice) -> VoiceInfo {
+        let language = voice
+            .language_codes
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "en-US".to_string());
+
         VoiceInfo {
             id: voice.name.clone(),
-            name: voice.display_name.clone(),
-            language: voice.language_code.clone(),
-            additional_languages: vec![],
-            gender: parse_gender(&voice.gender),
+            name: voice.name.clone(),
+            language,
+            additional_languages: voice.language_codes.iter().skip(1).cloned().collect(),
+            gender: parse_gender(&voice.ssml_gender),
             quality: VoiceQuality::Neural,
-            description: Some(format!("{} voice", voice.gender)),
+            description: Some(format!(
+                "{} voice ({} Hz)",
+                voice.ssml_gender, voice.natural_sample_rate_hertz
+            )),
             provider: "Google Cloud TTS".to_string(),
-            sample_rate: 24000,
+            sample_rate: voice.natural_sample_rate_hertz,
             is_custom: false,
             is_cloned: false,
             preview_url: None,
@@ -47,34 +103,35 @@ impl GoogleComponent {
 }
 
 impl VoicesGuest for GoogleComponent {
-    fn list_voices(_filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
+    fn list_voices(filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Google: Listing voices");
-        let voices = GoogleTtsClient::list_voices();
-        Ok(voices.iter().map(|v| Self::voice_to_info(v)).collect())
+        let voices = Self::cached_voices()?;
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            None,
+            filter.as_ref(),
+        ))
     }
 
     fn get_voice(voice_id: String) -> Result<VoiceInfo, TtsError> {
         trace!("Google: Getting voice {}", voice_id);
-        let voices = GoogleTtsClient::list_voices();
-        voices
-            .iter()
-            .find(|v| v.name == voice_id)
-            .map(|v| Self::voice_to_info(v))
+        Self::cached_voices()?
+            .into_iter()
+            .find(|v| v.id == voice_id)
             .ok_or_else(|| voice_not_found(voice_id))
     }
 
     fn search_voices(
         query: String,
-        _filter: Option<VoiceFilter>,
+        filter: Option<VoiceFilter>,
     ) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Google: Searching voices: {}", query);
-        let voices = GoogleTtsClient::list_voices();
-        let query_lower = query.to_lowercase();
-        Ok(voices
-            .iter()
-            .filter(|v| v.display_name.to_lowercase().contains(&query_lower))
-            .map(|v| Self::voice_to_info(v))
-            .collect())
+        let voices = Self::cached_voices()?;
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            Some(&query),
+            filter.as_ref(),
+        ))
     }
 
     fn list_languages() -> Result<Vec<LanguageInfo>, TtsError> {
@@ -99,7 +156,39 @@ impl SynthesisGuest for GoogleComponent {
         }
 
         let client = Self::create_client()?;
-        let audio_data = client.synthesize_speech(&input.content, &options.voice_id, "en-US")?;
+
+        let format = options
+            .audio_config
+            .as_ref()
+            .map(|c| c.format)
+            .unwrap_or(AudioFormat::Mp3);
+        let audio_encoding = audio_format_to_google(format);
+        let tuning = client::AudioTuning {
+            speaking_rate: options.voice_settings.as_ref().and_then(|v| v.speed),
+            pitch: options.voice_settings.as_ref().and_then(|v| v.pitch),
+            volume_gain_db: options.voice_settings.as_ref().and_then(|v| v.volume),
+            sample_rate_hertz: options.audio_config.as_ref().and_then(|c| c.sample_rate),
+        };
+
+        let language_code = Self::language_code_for_voice(&options.voice_id)?;
+
+        let audio_data = match input.text_type {
+            TextType::Ssml => client.synthesize_speech(
+                client::SynthesisInput::Ssml(input.content.clone()),
+                &options.voice_id,
+                &language_code,
+                audio_encoding,
+                &tuning,
+            )?,
+            _ => client.synthesize_long(
+                &input.content,
+                &options.voice_id,
+                &language_code,
+                chunking::DEFAULT_MAX_CHUNK_CHARS,
+                audio_encoding,
+                &tuning,
+            )?,
+        };
 
         Ok(SynthesisResult {
             audio_data,
@@ -244,6 +333,11 @@ impl AdvancedGuest for GoogleComponent {
     }
 }
 
+impl AudioQueryGuest for GoogleComponent {}
+
+impl VocabularyFilterGuest for GoogleComponent {}
+impl DictionaryGuest for GoogleComponent {}
+
 impl ExtendedGuest for GoogleComponent {}
 
 type DurableGoogleComponent = DurableTts<GoogleComponent>;
@@ -5,9 +5,12 @@ use golem_tts::golem::tts::types::{
     TextInput as WitTextInput, TimingInfo as WitTimingInfo, TtsError as WitTtsError,
 };
 use golem_tts::http::WstdHttpClient;
+use golem_tts::lexicon::{Lexicon, LexiconEntry, MatchMode};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use super::client::PollyClient;
+use crate::conversions::parse_polly_error;
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -20,11 +23,55 @@ pub struct SpeechMark {
     pub value: String,
 }
 
+/// Which `SpeechMarkTypes` to request from Polly's `SynthesizeSpeech` when
+/// `OutputFormat=json`. Viseme marks require a voice that ships mouth-shape
+/// data (the standard-tier neural voices do; check AWS's per-voice feature
+/// table before requesting them for an arbitrary voice).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechMarkType {
+    Sentence,
+    Word,
+    Viseme,
+    Ssml,
+}
+
+impl SpeechMarkType {
+    fn as_polly_str(self) -> &'static str {
+        match self {
+            SpeechMarkType::Sentence => "sentence",
+            SpeechMarkType::Word => "word",
+            SpeechMarkType::Viseme => "viseme",
+            SpeechMarkType::Ssml => "ssml",
+        }
+    }
+}
+
 impl PollyClient {
+    /// The default marks requested by the standalone `get_timing_marks`
+    /// guest call: sentence- and word-level timing, the pair useful for
+    /// caption alignment without requiring a viseme-capable voice.
     pub fn get_speech_marks(
         &self,
         input: WitTextInput,
         voice_id: String,
+    ) -> Result<Vec<WitTimingInfo>, WitTtsError> {
+        self.get_speech_marks_of_types(
+            input,
+            voice_id,
+            "neural",
+            &[SpeechMarkType::Sentence, SpeechMarkType::Word],
+        )
+    }
+
+    /// As [`Self::get_speech_marks`], but with the mark types and engine
+    /// the caller chooses, so a combined audio+marks synthesis can keep
+    /// both requests on the same engine.
+    pub fn get_speech_marks_of_types(
+        &self,
+        input: WitTextInput,
+        voice_id: String,
+        engine: &str,
+        mark_types: &[SpeechMarkType],
     ) -> Result<Vec<WitTimingInfo>, WitTtsError> {
         let http = WstdHttpClient::new();
 
@@ -42,8 +89,8 @@ impl PollyClient {
             text: input.content.clone(),
             output_format: "json".to_string(),
             voice_id,
-            speech_mark_types: vec!["word".to_string(), "sentence".to_string()],
-            engine: "neural".to_string(),
+            speech_mark_types: mark_types.iter().map(|t| t.as_polly_str().to_string()).collect(),
+            engine: engine.to_string(),
         };
 
         let json_payload = serde_json::to_string(&request_body).map_err(|e| Error::Json(e))?;
@@ -65,7 +112,7 @@ impl PollyClient {
         }
 
         let response = http_request
-            .body(json_payload)?
+            .body(json_payload.into_bytes())
             .send()?
             .error_for_status()?;
 
@@ -93,6 +140,12 @@ impl PollyClient {
     }
 
     pub fn put_lexicon(&self, name: String, content: String) -> Result<(), WitTtsError> {
+        if !is_well_formed_pls(&content) {
+            return Err(WitTtsError::InvalidInput(
+                "Lexicon content is not a well-formed <lexicon> PLS document".to_string(),
+            ));
+        }
+
         let http = WstdHttpClient::new();
 
         let host = self
@@ -113,7 +166,11 @@ impl PollyClient {
             http_request = http_request.header(k, &v);
         }
 
-        http_request.body(content)?.send()?.error_for_status()?;
+        let response = http_request.body(content.into_bytes()).send()?;
+        if response.status >= 400 {
+            let status = response.status;
+            return Err(parse_polly_error(status, &response.text()?));
+        }
 
         Ok(())
     }
@@ -137,8 +194,148 @@ impl PollyClient {
             http_request = http_request.header(k, &v);
         }
 
-        let response = http_request.send()?.error_for_status()?;
+        let response = http_request.send()?;
+        if response.status >= 400 {
+            let status = response.status;
+            return Err(parse_polly_error(status, &response.text()?));
+        }
 
         Ok(response.text()?)
     }
+
+    /// `ListLexicons` — the names of every lexicon stored under this
+    /// account/region.
+    pub fn list_lexicons(&self) -> Result<Vec<String>, WitTtsError> {
+        let http = WstdHttpClient::new();
+
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+        let headers = vec![("host", host)];
+
+        let signed_headers = self.sign_request("GET", "/v1/lexicons", "", &headers, "")?;
+
+        let url = format!("{}/v1/lexicons", self.base_url);
+        let mut http_request = http.get(&url);
+
+        for (k, v) in signed_headers {
+            http_request = http_request.header(k, &v);
+        }
+
+        let response = http_request.send()?;
+        if response.status >= 400 {
+            let status = response.status;
+            return Err(parse_polly_error(status, &response.text()?));
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct LexiconDescription {
+            name: String,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct ListLexiconsResponse {
+            lexicons: Vec<LexiconDescription>,
+        }
+
+        let parsed: ListLexiconsResponse =
+            serde_json::from_str(&response.text()?).map_err(Error::Json)?;
+        Ok(parsed.lexicons.into_iter().map(|l| l.name).collect())
+    }
+
+    pub fn delete_lexicon(&self, name: String) -> Result<(), WitTtsError> {
+        let http = WstdHttpClient::new();
+
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+        let uri = format!("/v1/lexicons/{}", name);
+        let headers = vec![("host", host)];
+
+        let signed_headers = self.sign_request("DELETE", &uri, "", &headers, "")?;
+
+        let url = format!("{}{}", self.base_url, uri);
+        let mut http_request = http.delete(&url);
+
+        for (k, v) in signed_headers {
+            http_request = http_request.header(k, &v);
+        }
+
+        let response = http_request.send()?;
+        if response.status >= 400 {
+            let status = response.status;
+            return Err(parse_polly_error(status, &response.text()?));
+        }
+
+        Ok(())
+    }
+}
+
+/// A minimal well-formedness check for a PLS upload: Polly itself
+/// validates the schema, but catching an obviously-wrong payload here
+/// avoids a round trip for the common mistake of passing JSON or a bare
+/// fragment instead of a `<lexicon>` document.
+fn is_well_formed_pls(content: &str) -> bool {
+    let trimmed = content.trim();
+    trimmed.contains("<lexicon") && trimmed.ends_with("</lexicon>")
+}
+
+/// Parse a PLS document in the shape [`Lexicon::export_pls`] produces
+/// (one `<grapheme>` plus one `<phoneme>` or `<alias>` per `<lexeme>`)
+/// back into a [`Lexicon`], so `add_lexicon_entry`/`remove_lexicon_entry`
+/// can mutate a single entry before the whole document is re-`PutLexicon`-ed.
+pub fn parse_pls(name: &str, xml: &str) -> Lexicon {
+    let lang = Regex::new(r#"xml:lang="([^"]*)""#)
+        .unwrap()
+        .captures(xml)
+        .map(|caps| xml_unescape(&caps[1]))
+        .unwrap_or_default();
+
+    let lexeme_re = Regex::new(r"(?s)<lexeme>(.*?)</lexeme>").unwrap();
+    let grapheme_re = Regex::new(r"(?s)<grapheme>(.*?)</grapheme>").unwrap();
+    let phoneme_re = Regex::new(r"(?s)<phoneme>(.*?)</phoneme>").unwrap();
+    let alias_re = Regex::new(r"(?s)<alias>(.*?)</alias>").unwrap();
+
+    let entries = lexeme_re
+        .captures_iter(xml)
+        .filter_map(|block_caps| {
+            let block = &block_caps[1];
+            let word = xml_unescape(&grapheme_re.captures(block)?[1]);
+            let (replacement, phonetic) = match (
+                phoneme_re.captures(block),
+                alias_re.captures(block),
+            ) {
+                (Some(phoneme), _) => (word.clone(), Some(xml_unescape(&phoneme[1]))),
+                (None, Some(alias)) => (xml_unescape(&alias[1]), None),
+                (None, None) => return None,
+            };
+            Some(LexiconEntry {
+                word,
+                replacement,
+                phonetic,
+                match_mode: MatchMode::CaseInsensitive,
+                accent: None,
+                word_type: None,
+                priority: None,
+            })
+        })
+        .collect();
+
+    Lexicon {
+        id: name.to_string(),
+        name: name.to_string(),
+        language: lang,
+        entries,
+    }
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
 }
@@ -1,11 +1,12 @@
+use golem_tts::config::{get_config_or_default, get_optional_config};
 use golem_tts::error::Error;
 use golem_tts::exports::golem::tts::synthesis::SynthesisOptions as WitSynthesisOptions;
 use golem_tts::exports::golem::tts::voices::{
     LanguageInfo as WitLanguageInfo, VoiceFilter as WitVoiceFilter, VoiceInfo as WitVoiceInfo,
 };
 use golem_tts::golem::tts::types::{
-    SynthesisResult as WitSynthesisResult, TextInput as WitTextInput, TtsError as WitTtsError,
-    VoiceGender, VoiceQuality,
+    SynthesisResult as WitSynthesisResult, TextInput as WitTextInput, TimingInfo as WitTimingInfo,
+    TtsError as WitTtsError,
 };
 use golem_tts::http::WstdHttpClient;
 use hmac::{Hmac, Mac};
@@ -13,32 +14,87 @@ use log::trace;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use crate::advanced::SpeechMarkType;
+use crate::conversions::{
+    infer_quality_from_engines, parse_gender, recommended_engine_for_voice, validate_sample_rate,
+};
+use crate::credentials::{CredentialProvider, EnvCredentialProvider};
+
 type HmacSha256 = Hmac<Sha256>;
 
+/// One entry of a `DescribeVoices` response, AWS's field names preserved.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PollyVoice {
+    id: String,
+    name: String,
+    gender: String,
+    language_code: String,
+    language_name: String,
+    supported_engines: Vec<String>,
+    #[serde(default)]
+    additional_language_codes: Vec<String>,
+}
+
+impl PollyVoice {
+    fn into_wit(self) -> WitVoiceInfo {
+        WitVoiceInfo {
+            id: self.id,
+            name: self.name,
+            language: self.language_code.clone(),
+            additional_languages: self.additional_language_codes,
+            gender: parse_gender(&self.gender),
+            quality: infer_quality_from_engines(&self.supported_engines),
+            description: Some(format!("{} voice", self.language_name)),
+            provider: "AWS Polly".to_string(),
+            sample_rate: 24000,
+            is_custom: false,
+            is_cloned: false,
+            preview_url: None,
+            use_cases: vec!["general".to_string()],
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DescribeVoicesResponse {
+    voices: Vec<PollyVoice>,
+    next_token: Option<String>,
+}
+
 pub struct PollyClient {
-    access_key_id: String,
-    secret_access_key: String,
-    session_token: Option<String>,
+    credential_provider: Box<dyn CredentialProvider>,
     region: String,
     pub(crate) base_url: String,
 }
 
 impl PollyClient {
-    pub fn new(
-        access_key_id: String,
-        secret_access_key: String,
+    /// Build a client that re-reads AWS credentials from the standard
+    /// environment variables on every signed request (see
+    /// [`EnvCredentialProvider`]). `AWS_ACCESS_KEY_ID` and
+    /// `AWS_SECRET_ACCESS_KEY` are required, `AWS_REGION` defaults to
+    /// `us-east-1`, and `AWS_SESSION_TOKEN` is only sent when temporary
+    /// credentials are in use.
+    pub fn new() -> Result<Self, WitTtsError> {
+        let region = get_config_or_default("AWS_REGION", "us-east-1");
+        Self::with_credential_provider(Box::new(EnvCredentialProvider), region)
+    }
+
+    /// Build a client against a caller-supplied [`CredentialProvider`]
+    /// (e.g. a `StaticCredentialProvider` wrapping a key pair obtained
+    /// some other way) instead of the standard AWS environment variables.
+    pub fn with_credential_provider(
+        credential_provider: Box<dyn CredentialProvider>,
         region: String,
-        session_token: Option<String>,
-    ) -> Self {
+    ) -> Result<Self, WitTtsError> {
         let base_url = format!("https://polly.{}.amazonaws.com", region);
 
-        Self {
-            access_key_id,
-            secret_access_key,
-            session_token,
+        Ok(Self {
+            credential_provider,
             region,
             base_url,
-        }
+        })
     }
 
     pub(crate) fn sign_request(
@@ -51,27 +107,35 @@ impl PollyClient {
     ) -> Result<Vec<(&'static str, String)>, WitTtsError> {
         // AWS Signature Version 4 signing process
         let service = "polly";
+        let credentials = self.credential_provider.credentials()?;
 
-        // Get timestamp
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| WitTtsError::InternalError(format!("Time error: {}", e)))?;
-        let timestamp = format!("{}", now.as_secs());
-        let amz_date = format!("{}000", timestamp); // Simplified - should be YYYYMMDD'T'HHMMSS'Z'
-
-        // Task 1: Create canonical request
-        let mut canonical_headers = String::new();
-        let mut signed_headers = Vec::new();
-
-        for (k, v) in headers {
-            canonical_headers.push_str(&format!("{}:{}\n", k.to_lowercase(), v));
-            signed_headers.push(k.to_lowercase());
-        }
-        signed_headers.sort();
-        let signed_headers_str = signed_headers.join(";");
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
 
         let payload_hash = hex::encode(Sha256::digest(payload.as_bytes()));
 
+        // Task 1: Create canonical request. `x-amz-content-sha256` is
+        // folded in alongside the caller-supplied headers so the payload
+        // hash itself is part of what gets signed, and the whole set is
+        // sorted by header name as SigV4 requires.
+        let mut canonical_header_pairs: Vec<(String, String)> = headers
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.to_string()))
+            .collect();
+        canonical_header_pairs.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+        canonical_header_pairs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let canonical_headers: String = canonical_header_pairs
+            .iter()
+            .map(|(k, v)| format!("{}:{}\n", k, v))
+            .collect();
+        let signed_headers_str = canonical_header_pairs
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
         let canonical_request = format!(
             "{}\n{}\n{}\n{}\n{}\n{}",
             method, uri, query_string, canonical_headers, signed_headers_str, payload_hash
@@ -80,12 +144,7 @@ impl PollyClient {
         let canonical_request_hash = hex::encode(Sha256::digest(canonical_request.as_bytes()));
 
         // Task 2: Create string to sign
-        let credential_scope = format!(
-            "{}/{}/{}/aws4_request",
-            &amz_date[..8],
-            self.region,
-            service
-        );
+        let credential_scope = format!("{}/{}/{}/aws4_request", date_stamp, self.region, service);
         let string_to_sign = format!(
             "AWS4-HMAC-SHA256\n{}\n{}\n{}",
             amz_date, credential_scope, canonical_request_hash
@@ -93,8 +152,8 @@ impl PollyClient {
 
         // Task 3: Calculate signature
         let date_key = self.hmac_sha256(
-            format!("AWS4{}", self.secret_access_key).as_bytes(),
-            &amz_date[..8],
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            &date_stamp,
         );
         let date_region_key = self.hmac_sha256(&date_key, &self.region);
         let date_region_service_key = self.hmac_sha256(&date_region_key, service);
@@ -105,13 +164,17 @@ impl PollyClient {
         // Task 4: Create authorization header
         let authorization = format!(
             "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
-            self.access_key_id, credential_scope, signed_headers_str, signature
+            credentials.access_key_id, credential_scope, signed_headers_str, signature
         );
 
-        let mut result_headers = vec![("Authorization", authorization), ("X-Amz-Date", amz_date)];
+        let mut result_headers = vec![
+            ("Authorization", authorization),
+            ("X-Amz-Date", amz_date),
+            ("X-Amz-Content-Sha256", payload_hash),
+        ];
 
-        if let Some(ref token) = self.session_token {
-            result_headers.push(("X-Amz-Security-Token", token.clone()));
+        if let Some(token) = credentials.session_token {
+            result_headers.push(("X-Amz-Security-Token", token));
         }
 
         Ok(result_headers)
@@ -123,162 +186,61 @@ impl PollyClient {
         mac.finalize().into_bytes().to_vec()
     }
 
+    /// Raw `DescribeVoices` entries, AWS's own field names preserved
+    /// (`list_voices`/`list_languages` each project this into the WIT
+    /// shape they need). Paginates via `NextToken` until the response
+    /// omits one.
+    fn describe_voices(&self) -> Result<Vec<PollyVoice>, WitTtsError> {
+        let http = WstdHttpClient::new();
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+
+        let mut voices = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let query_string = next_token
+                .as_ref()
+                .map(|t| format!("NextToken={}", t))
+                .unwrap_or_default();
+            let headers = vec![("host", host)];
+            let signed_headers =
+                self.sign_request("GET", "/v1/voices", &query_string, &headers, "")?;
+
+            let url = if query_string.is_empty() {
+                format!("{}/v1/voices", self.base_url)
+            } else {
+                format!("{}/v1/voices?{}", self.base_url, query_string)
+            };
+            let mut http_request = http.get(&url);
+            for (k, v) in signed_headers {
+                http_request = http_request.header(k, &v);
+            }
+
+            let response = http_request.send()?.error_for_status()?;
+            let page: DescribeVoicesResponse =
+                serde_json::from_str(&response.text()?).map_err(Error::Json)?;
+
+            next_token = page.next_token;
+            voices.extend(page.voices);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(voices)
+    }
+
+    /// Live voice catalog via `GET /v1/voices`.
     pub fn list_voices(&self) -> Result<Vec<WitVoiceInfo>, WitTtsError> {
         trace!("Listing AWS Polly voices");
-
-        // Popular Polly voices
-        Ok(vec![
-            WitVoiceInfo {
-                id: "Joanna".to_string(),
-                name: "Joanna".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("US English female voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string(), "assistant".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Matthew".to_string(),
-                name: "Matthew".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Male,
-                quality: VoiceQuality::Neural,
-                description: Some("US English male voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string(), "professional".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Ivy".to_string(),
-                name: "Ivy".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("US English child's voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["conversational".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Kendra".to_string(),
-                name: "Kendra".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("US English female voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Kevin".to_string(),
-                name: "Kevin".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Male,
-                quality: VoiceQuality::Neural,
-                description: Some("US English child's voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["conversational".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Salli".to_string(),
-                name: "Salli".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("US English female voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Joey".to_string(),
-                name: "Joey".to_string(),
-                language: "en-US".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Male,
-                quality: VoiceQuality::Neural,
-                description: Some("US English male voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Amy".to_string(),
-                name: "Amy".to_string(),
-                language: "en-GB".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("British English female voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Brian".to_string(),
-                name: "Brian".to_string(),
-                language: "en-GB".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Male,
-                quality: VoiceQuality::Neural,
-                description: Some("British English male voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string()],
-            },
-            WitVoiceInfo {
-                id: "Emma".to_string(),
-                name: "Emma".to_string(),
-                language: "en-GB".to_string(),
-                additional_languages: vec![],
-                gender: VoiceGender::Female,
-                quality: VoiceQuality::Neural,
-                description: Some("British English female voice".to_string()),
-                provider: "AWS Polly".to_string(),
-                sample_rate: 24000,
-                is_custom: false,
-                is_cloned: false,
-                preview_url: None,
-                use_cases: vec!["general".to_string(), "news".to_string()],
-            },
-        ])
+        Ok(self
+            .describe_voices()?
+            .into_iter()
+            .map(PollyVoice::into_wit)
+            .collect())
     }
 
     pub fn get_voice(&self, voice_id: String) -> Result<WitVoiceInfo, WitTtsError> {
@@ -323,33 +285,70 @@ impl PollyClient {
             .collect())
     }
 
+    /// Groups the live voice catalog by `LanguageCode`, so newly added
+    /// Polly languages show up here automatically.
     pub fn list_languages(&self) -> Result<Vec<WitLanguageInfo>, WitTtsError> {
-        Ok(vec![
-            WitLanguageInfo {
-                code: "en-US".to_string(),
-                name: "English (US)".to_string(),
-                native_name: "English (US)".to_string(),
-                voice_count: 7,
-            },
-            WitLanguageInfo {
-                code: "en-GB".to_string(),
-                name: "English (UK)".to_string(),
-                native_name: "English (UK)".to_string(),
-                voice_count: 3,
-            },
-        ])
+        let voices = self.describe_voices()?;
+
+        let mut languages: Vec<WitLanguageInfo> = Vec::new();
+        for voice in &voices {
+            match languages
+                .iter_mut()
+                .find(|l| l.code == voice.language_code)
+            {
+                Some(lang) => lang.voice_count += 1,
+                None => languages.push(WitLanguageInfo {
+                    code: voice.language_code.clone(),
+                    name: voice.language_name.clone(),
+                    native_name: voice.language_name.clone(),
+                    voice_count: 1,
+                }),
+            }
+        }
+
+        Ok(languages)
+    }
+
+    /// The `Engine` a `synthesize`/`start_synthesis_task` call should use
+    /// for `voice_id`: the `AWS_POLLY_ENGINE` environment variable when
+    /// set, otherwise [`recommended_engine_for_voice`], checked against
+    /// the voice's actual `SupportedEngines` from `DescribeVoices` so a
+    /// stale override or a guess that's no longer valid fails clearly
+    /// instead of being silently sent to AWS.
+    fn resolve_engine(&self, voice_id: &str) -> Result<String, WitTtsError> {
+        let voices = self.describe_voices()?;
+        let voice = voices
+            .iter()
+            .find(|v| v.id == voice_id)
+            .ok_or_else(|| WitTtsError::VoiceNotFound(voice_id.to_string()))?;
+
+        let engine = get_optional_config("AWS_POLLY_ENGINE")
+            .unwrap_or_else(|| recommended_engine_for_voice(voice_id).to_string());
+
+        if !voice.supported_engines.iter().any(|e| e == &engine) {
+            return Err(WitTtsError::InvalidConfiguration(format!(
+                "Voice '{}' does not support engine '{}' (supports: {})",
+                voice_id,
+                engine,
+                voice.supported_engines.join(", ")
+            )));
+        }
+
+        Ok(engine)
     }
 
     pub fn synthesize(
         &self,
         input: WitTextInput,
         options: WitSynthesisOptions,
+        lexicon_names: &[String],
     ) -> Result<WitSynthesisResult, WitTtsError> {
         trace!(
             "Synthesizing speech with AWS Polly voice {}",
             options.voice_id
         );
 
+        let engine = self.resolve_engine(&options.voice_id)?;
         let http = WstdHttpClient::new();
 
         #[derive(Serialize)]
@@ -359,20 +358,24 @@ impl PollyClient {
             output_format: String,
             voice_id: String,
             engine: String,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            sample_rate: Option<String>,
+            sample_rate: String,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            lexicon_names: Vec<String>,
         }
 
+        let sample_rate = validate_sample_rate(
+            options.audio_config.as_ref().and_then(|c| c.sample_rate),
+            "mp3",
+            &engine,
+        );
+
         let request_body = SynthesizeSpeechRequest {
             text: input.content.clone(),
             output_format: "mp3".to_string(),
             voice_id: options.voice_id.clone(),
-            engine: "neural".to_string(),
-            sample_rate: options
-                .audio_config
-                .as_ref()
-                .and_then(|c| c.sample_rate)
-                .map(|sr| sr.to_string()),
+            engine: engine.clone(),
+            sample_rate: sample_rate.to_string(),
+            lexicon_names: lexicon_names.to_vec(),
         };
 
         let json_payload = serde_json::to_string(&request_body).map_err(|e| Error::Json(e))?;
@@ -422,7 +425,189 @@ impl PollyClient {
     ) -> Result<Vec<WitSynthesisResult>, WitTtsError> {
         inputs
             .into_iter()
-            .map(|input| self.synthesize(input, options.clone()))
+            .map(|input| self.synthesize(input, options.clone(), &[]))
             .collect()
     }
+
+    /// As [`Self::synthesize`], but also requests `mark_types` via a
+    /// second `OutputFormat=json` call on the same engine the audio was
+    /// synthesized with, for lip-sync/karaoke/caption use cases that need
+    /// both the audio and its timing in one round trip.
+    pub fn synthesize_with_marks(
+        &self,
+        input: WitTextInput,
+        options: WitSynthesisOptions,
+        lexicon_names: &[String],
+        mark_types: &[SpeechMarkType],
+    ) -> Result<(WitSynthesisResult, Vec<WitTimingInfo>), WitTtsError> {
+        // Resolve with the same logic `synthesize` uses so the marks
+        // request below lands on the engine picked for the audio, even when
+        // that comes from `AWS_POLLY_ENGINE` rather than the voice's
+        // recommendation.
+        let engine = self.resolve_engine(&options.voice_id)?;
+        let result = self.synthesize(input.clone(), options.clone(), lexicon_names)?;
+
+        if mark_types.is_empty() {
+            return Ok((result, Vec::new()));
+        }
+
+        let marks =
+            self.get_speech_marks_of_types(input, options.voice_id, &engine, mark_types)?;
+        Ok((result, marks))
+    }
+
+    /// Kick off an asynchronous Polly synthesis task via
+    /// `StartSpeechSynthesisTask`, writing the finished audio to
+    /// `s3_bucket`/`s3_key_prefix` instead of returning it inline.
+    pub fn start_synthesis_task(
+        &self,
+        text: &str,
+        voice_id: &str,
+        s3_bucket: &str,
+        s3_key_prefix: Option<&str>,
+    ) -> Result<PollySynthesisTask, WitTtsError> {
+        let engine = self.resolve_engine(voice_id)?;
+        let http = WstdHttpClient::new();
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct StartSynthesisTaskRequest<'a> {
+            text: &'a str,
+            output_format: &'a str,
+            voice_id: &'a str,
+            engine: &'a str,
+            output_s3_bucket_name: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            output_s3_key_prefix: Option<&'a str>,
+        }
+
+        let request_body = StartSynthesisTaskRequest {
+            text,
+            output_format: "mp3",
+            voice_id,
+            engine: &engine,
+            output_s3_bucket_name: s3_bucket,
+            output_s3_key_prefix: s3_key_prefix,
+        };
+
+        let json_payload = serde_json::to_string(&request_body).map_err(Error::Json)?;
+
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+        let headers = vec![("host", host), ("content-type", "application/json")];
+
+        let signed_headers =
+            self.sign_request("POST", "/v1/synthesisTasks", "", &headers, &json_payload)?;
+
+        let url = format!("{}/v1/synthesisTasks", self.base_url);
+        let mut http_request = http.post(&url).header("Content-Type", "application/json");
+        for (k, v) in signed_headers {
+            http_request = http_request.header(k, &v);
+        }
+
+        let response = http_request
+            .body(json_payload.into_bytes())
+            .send()?
+            .error_for_status()?;
+
+        let envelope: SynthesisTaskEnvelope = response.json()?;
+        Ok(envelope.synthesis_task)
+    }
+
+    /// Poll a previously started task via `GetSpeechSynthesisTask`.
+    pub fn get_synthesis_task(&self, task_id: &str) -> Result<PollySynthesisTask, WitTtsError> {
+        let http = WstdHttpClient::new();
+
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+        let uri = format!("/v1/synthesisTasks/{}", task_id);
+        let headers = vec![("host", host)];
+
+        let signed_headers = self.sign_request("GET", &uri, "", &headers, "")?;
+
+        let url = format!("{}{}", self.base_url, uri);
+        let mut http_request = http.get(&url);
+        for (k, v) in signed_headers {
+            http_request = http_request.header(k, &v);
+        }
+
+        let response = http_request.send()?.error_for_status()?;
+        let envelope: SynthesisTaskEnvelope = response.json()?;
+        Ok(envelope.synthesis_task)
+    }
+
+    /// List every in-flight or completed synthesis task via
+    /// `ListSpeechSynthesisTasks`. AWS paginates this with `NextToken`,
+    /// so we keep following it until the response omits one.
+    pub fn list_synthesis_tasks(&self) -> Result<Vec<PollySynthesisTask>, WitTtsError> {
+        let http = WstdHttpClient::new();
+        let host = self
+            .base_url
+            .strip_prefix("https://")
+            .unwrap_or(&self.base_url);
+
+        let mut tasks = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let query_string = next_token
+                .as_ref()
+                .map(|t| format!("NextToken={}", t))
+                .unwrap_or_default();
+            let headers = vec![("host", host)];
+            let signed_headers =
+                self.sign_request("GET", "/v1/synthesisTasks", &query_string, &headers, "")?;
+
+            let url = if query_string.is_empty() {
+                format!("{}/v1/synthesisTasks", self.base_url)
+            } else {
+                format!("{}/v1/synthesisTasks?{}", self.base_url, query_string)
+            };
+            let mut http_request = http.get(&url);
+            for (k, v) in signed_headers {
+                http_request = http_request.header(k, &v);
+            }
+
+            let response = http_request.send()?.error_for_status()?;
+            let page: ListSynthesisTasksResponse = response.json()?;
+
+            next_token = page.next_token;
+            tasks.extend(page.synthesis_tasks);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(tasks)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListSynthesisTasksResponse {
+    synthesis_tasks: Vec<PollySynthesisTask>,
+    next_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SynthesisTaskEnvelope {
+    synthesis_task: PollySynthesisTask,
+}
+
+/// A Polly `SynthesisTask` record, as returned by both
+/// `StartSpeechSynthesisTask` and `GetSpeechSynthesisTask`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct PollySynthesisTask {
+    pub task_id: String,
+    pub task_status: String,
+    #[serde(default)]
+    pub task_status_reason: Option<String>,
+    #[serde(default)]
+    pub output_uri: Option<String>,
 }
@@ -1,5 +1,5 @@
 // Type conversions for AWS Polly
-use golem_tts::golem::tts::types::{AudioFormat, TtsError as WitTtsError};
+use golem_tts::golem::tts::types::{AudioFormat, TtsError as WitTtsError, VoiceGender, VoiceQuality};
 
 /// Convert AudioFormat to AWS Polly output format
 pub fn audio_format_to_polly(format: AudioFormat) -> &'static str {
@@ -22,12 +22,51 @@ pub fn recommended_engine_for_voice(voice_id: &str) -> &'static str {
     }
 }
 
-/// Convert sample rate to valid Polly format
-pub fn validate_sample_rate(rate: Option<u32>, format: &str) -> u32 {
+/// Parse a Polly `DescribeVoices` gender string ("Male"/"Female") into
+/// [`VoiceGender`].
+pub fn parse_gender(gender: &str) -> VoiceGender {
+    match gender.to_lowercase().as_str() {
+        "male" => VoiceGender::Male,
+        "female" => VoiceGender::Female,
+        _ => VoiceGender::Neutral,
+    }
+}
+
+/// Derive [`VoiceQuality`] from a voice's `SupportedEngines` list: voices
+/// that support the `generative` engine are the newest tier, `neural`
+/// voices are the common case, and anything left only offers the legacy
+/// `standard` engine.
+pub fn infer_quality_from_engines(engines: &[String]) -> VoiceQuality {
+    if engines.iter().any(|e| e == "generative") {
+        VoiceQuality::Premium
+    } else if engines.iter().any(|e| e == "neural") {
+        VoiceQuality::Neural
+    } else {
+        VoiceQuality::Standard
+    }
+}
+
+const VALID_COMPRESSED_RATES: [u32; 4] = [8000, 16000, 22050, 24000];
+const VALID_PCM_RATES: [u32; 2] = [8000, 16000];
+
+/// Clamp a requested sample rate to one Polly actually accepts for
+/// `format`/`engine`, falling back to a sensible default when the request
+/// is empty or off the supported list. The `generative` and `long-form`
+/// engines only ever render MP3/OGG at Polly's top sample rate, so those
+/// two ignore the caller's request rather than silently downsampling.
+pub fn validate_sample_rate(rate: Option<u32>, format: &str, engine: &str) -> u32 {
     match format {
-        "mp3" => 24000,                 // MP3 supports 8000, 16000, 22050, 24000
-        "ogg_vorbis" => 24000,          // OGG supports 8000, 16000, 22050, 24000
-        "pcm" => rate.unwrap_or(16000), // PCM supports 8000, 16000, 24000
+        "pcm" => rate
+            .filter(|r| VALID_PCM_RATES.contains(r))
+            .unwrap_or(16000),
+        "mp3" | "ogg_vorbis" => {
+            if engine == "generative" || engine == "long-form" {
+                24000
+            } else {
+                rate.filter(|r| VALID_COMPRESSED_RATES.contains(r))
+                    .unwrap_or(24000)
+            }
+        }
         _ => 24000,
     }
 }
@@ -43,13 +82,19 @@ pub fn parse_polly_error(status: u16, body: &str) -> WitTtsError {
                         WitTtsError::TextTooLong(3000)
                     } else if message.contains("SSML") {
                         WitTtsError::InvalidSsml(message.to_string())
+                    } else if message.contains("InvalidLexicon")
+                        || message.contains("MaxLexemeLength")
+                    {
+                        WitTtsError::InvalidInput(message.to_string())
                     } else {
                         WitTtsError::InvalidText(message.to_string())
                     }
                 }
                 403 => WitTtsError::Unauthorized("Invalid AWS credentials".to_string()),
                 404 => {
-                    if message.contains("voice") {
+                    if message.contains("LexiconNotFound") {
+                        WitTtsError::NotFound(message.to_string())
+                    } else if message.contains("voice") {
                         WitTtsError::VoiceNotFound(message.to_string())
                     } else {
                         WitTtsError::ServiceUnavailable(message.to_string())
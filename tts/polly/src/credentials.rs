@@ -0,0 +1,70 @@
+//! AWS credential sourcing for [`crate::client::PollyClient`].
+//!
+//! Unlike Google's OAuth2 flow (see the sibling `google` provider's
+//! `auth` module), obtaining AWS credentials requires no network round
+//! trip, so there's nothing to cache: [`EnvCredentialProvider`] simply
+//! re-reads the standard environment variables on every call, so a
+//! session token rotated into the environment by the host takes effect on
+//! the very next signed request without rebuilding the client.
+
+use golem_tts::config::{get_optional_config, with_config_key};
+use golem_tts::golem::tts::types::TtsError as WitTtsError;
+
+/// A single AWS key pair plus an optional STS session token, ready to
+/// sign a request with.
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+/// Source of [`AwsCredentials`] for [`crate::client::PollyClient`].
+/// Queried on every signed request rather than once at construction, so a
+/// provider backed by temporary credentials can hand back a fresh value
+/// without the caller having to rebuild the client.
+pub trait CredentialProvider {
+    fn credentials(&self) -> Result<AwsCredentials, WitTtsError>;
+}
+
+/// Fixed credentials supplied directly by the caller, e.g. when they come
+/// from something other than the standard AWS environment variables.
+pub struct StaticCredentialProvider(AwsCredentials);
+
+impl StaticCredentialProvider {
+    pub fn new(
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+    ) -> Self {
+        Self(AwsCredentials {
+            access_key_id,
+            secret_access_key,
+            session_token,
+        })
+    }
+}
+
+impl CredentialProvider for StaticCredentialProvider {
+    fn credentials(&self) -> Result<AwsCredentials, WitTtsError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Reads `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`/`AWS_SESSION_TOKEN`
+/// from the component's configuration on every call. A temporary
+/// session-token credential that the host rotates into the environment
+/// (e.g. an STS credential nearing expiry) is therefore picked up on the
+/// very next request, with no caching or expiry bookkeeping needed since
+/// reading configuration is local and has no network cost.
+pub struct EnvCredentialProvider;
+
+impl CredentialProvider for EnvCredentialProvider {
+    fn credentials(&self) -> Result<AwsCredentials, WitTtsError> {
+        Ok(AwsCredentials {
+            access_key_id: with_config_key("AWS_ACCESS_KEY_ID", Err, Ok)?,
+            secret_access_key: with_config_key("AWS_SECRET_ACCESS_KEY", Err, Ok)?,
+            session_token: get_optional_config("AWS_SESSION_TOKEN"),
+        })
+    }
+}
@@ -1,9 +1,16 @@
 // AWS Polly TTS provider
+mod advanced;
 mod client;
 mod conversions;
+mod credentials;
+mod long_form;
+mod segmentation;
+mod streaming;
 
-use client::{PollyClient, PollyVoice};
+use client::PollyClient;
 use conversions::*;
+use golem_tts::cache::VoiceCache;
+use golem_tts::config::parse_config_u32;
 use golem_tts::durability::{DurableTts, ExtendedGuest};
 use golem_tts::error::{invalid_text, unsupported, voice_not_found};
 use golem_tts::golem::tts::advanced::{
@@ -15,78 +22,104 @@ use golem_tts::golem::tts::synthesis::{
     Guest as SynthesisGuest, SynthesisOptions, ValidationResult,
 };
 use golem_tts::golem::tts::types::{
-    SynthesisResult, TextInput, TimingInfo, TtsError, VoiceQuality,
+    AudioChunk, SynthesisResult, TextInput, TimingInfo, TtsError, VoiceQuality,
 };
 use golem_tts::golem::tts::voices::{Guest as VoicesGuest, LanguageInfo, VoiceFilter, VoiceInfo};
+use golem_tts::guest::AudioQueryGuest;
+use golem_tts::guest::VocabularyFilterGuest;
+use golem_tts::guest::DictionaryGuest;
+use golem_tts::lexicon::{Lexicon, LexiconEntry};
 use log::{debug, info, trace};
+use long_form::LongFormTracker;
+use std::cell::RefCell;
+use streaming::StreamManager;
 
 struct PollyComponent;
 
+thread_local! {
+    static LONG_FORM: LongFormTracker = LongFormTracker::new();
+    static STREAM_MANAGER: StreamManager = StreamManager::new();
+
+    /// Names of every lexicon this component has `PutLexicon`-ed to AWS,
+    /// sent as `LexiconNames` on every `synthesize` call so registered
+    /// pronunciations apply. There is no guest hook to deregister one, so
+    /// this only ever grows within a component instance's lifetime.
+    static LEXICON_NAMES: RefCell<Vec<String>> = RefCell::new(Vec::new());
+
+    /// Cached full voice catalog (unfiltered), refreshed every
+    /// `TTS_VOICE_CACHE_TTL` seconds (default 300s) so `get_voice` and
+    /// `search_voices` don't each re-hit Polly's voice list.
+    static VOICE_CACHE: VoiceCache<Vec<VoiceInfo>> =
+        VoiceCache::new(parse_config_u32("TTS_VOICE_CACHE_TTL", 300) as u64);
+}
+
 impl PollyComponent {
     fn create_client() -> Result<PollyClient, TtsError> {
         PollyClient::new()
     }
 
-    fn voice_to_info(voice: &PollyVoice) -> VoiceInfo {
-        VoiceInfo {
-            id: voice.id.clone(),
-            name: voice.name.clone(),
-            language: voice.language_code.clone(),
-            additional_languages: vec![],
-            gender: parse_gender(&voice.gender),
-            quality: VoiceQuality::Neural,
-            description: Some(format!("{} voice", voice.gender)),
-            provider: "AWS Polly".to_string(),
-            sample_rate: 24000,
-            is_custom: false,
-            is_cloned: false,
-            preview_url: None,
-            use_cases: vec!["general".to_string()],
+    /// The full, unfiltered voice catalog, served from cache when valid.
+    fn cached_voices() -> Result<Vec<VoiceInfo>, TtsError> {
+        if let Some(voices) = VOICE_CACHE.with(|cache| cache.get()) {
+            return Ok(voices);
         }
+
+        let client = Self::create_client()?;
+        let voices = client.list_voices()?;
+        VOICE_CACHE.with(|cache| cache.set(voices.clone()));
+        Ok(voices)
     }
 }
 
 impl VoicesGuest for PollyComponent {
-    fn list_voices(_filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
+    fn list_voices(filter: Option<VoiceFilter>) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Polly: Listing voices");
-        let voices = PollyClient::list_voices();
-        Ok(voices.iter().map(|v| Self::voice_to_info(v)).collect())
+        let voices = Self::cached_voices()?;
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            None,
+            filter.as_ref(),
+        ))
     }
 
     fn get_voice(voice_id: String) -> Result<VoiceInfo, TtsError> {
         trace!("Polly: Getting voice {}", voice_id);
-        let voices = PollyClient::list_voices();
-        voices
-            .iter()
+        Self::cached_voices()?
+            .into_iter()
             .find(|v| v.id == voice_id)
-            .map(|v| Self::voice_to_info(v))
             .ok_or_else(|| voice_not_found(voice_id))
     }
 
     fn search_voices(
         query: String,
-        _filter: Option<VoiceFilter>,
+        filter: Option<VoiceFilter>,
     ) -> Result<Vec<VoiceInfo>, TtsError> {
         debug!("Polly: Searching voices: {}", query);
-        let voices = PollyClient::list_voices();
-        let query_lower = query.to_lowercase();
-        Ok(voices
-            .iter()
-            .filter(|v| v.name.to_lowercase().contains(&query_lower))
-            .map(|v| Self::voice_to_info(v))
-            .collect())
+        let voices = Self::cached_voices()?;
+        Ok(golem_tts::voice_filter::search_and_filter(
+            &voices,
+            Some(&query),
+            filter.as_ref(),
+        ))
     }
 
     fn list_languages() -> Result<Vec<LanguageInfo>, TtsError> {
-        Ok(vec![LanguageInfo {
-            code: "en-US".to_string(),
-            name: "English (US)".to_string(),
-            native_name: "English".to_string(),
-            voice_count: 4,
-        }])
+        debug!("Polly: Listing languages");
+        let client = Self::create_client()?;
+        client.list_languages()
     }
 }
 
+/// `SynthesizeSpeech` returns audio inline and caps out at this many
+/// characters; text beyond it has to go through `StartSpeechSynthesisTask`
+/// instead (exposed here as `AdvancedGuest::synthesize_long_form`, which
+/// accepts up to [`long_form::MAX_CHARS_PER_TASK`] chars per chapter and
+/// writes to S3 rather than returning audio inline). `synthesize` itself
+/// can't silently switch transports mid-call — it has no S3 destination to
+/// write to and must keep returning audio inline — so it reports
+/// `TextTooLong` and the caller is expected to use `synthesize_long_form`.
+const MAX_SYNC_CHARS: usize = 3000;
+
 impl SynthesisGuest for PollyComponent {
     fn synthesize(
         input: TextInput,
@@ -97,15 +130,13 @@ impl SynthesisGuest for PollyComponent {
         if input.content.is_empty() {
             return Err(invalid_text("Text cannot be empty"));
         }
+        if input.content.len() > MAX_SYNC_CHARS {
+            return Err(TtsError::TextTooLong(MAX_SYNC_CHARS as u32));
+        }
 
         let client = Self::create_client()?;
-        let format = audio_format_to_polly(options.audio_config.format);
-        let audio_data = client.synthesize_speech(&input.content, &options.voice_id, format)?;
-
-        Ok(SynthesisResult {
-            audio_data,
-            metadata: None,
-        })
+        let lexicon_names = LEXICON_NAMES.with(|cell| cell.borrow().clone());
+        client.synthesize(input, options, &lexicon_names)
     }
 
     fn synthesize_batch(
@@ -119,59 +150,83 @@ impl SynthesisGuest for PollyComponent {
             .collect()
     }
 
-    fn get_timing_marks(_input: TextInput, _voice_id: String) -> Result<Vec<TimingInfo>, TtsError> {
-        Err(unsupported("Polly timing marks require speech marks API"))
+    fn get_timing_marks(input: TextInput, voice_id: String) -> Result<Vec<TimingInfo>, TtsError> {
+        trace!("Polly: Getting timing marks for voice {}", voice_id);
+        let client = Self::create_client()?;
+        client.get_speech_marks(input, voice_id)
     }
 
     fn validate_input(input: TextInput, _voice_id: String) -> Result<ValidationResult, TtsError> {
         let char_count = input.content.len() as u32;
-        let is_valid = char_count > 0 && char_count <= 3000;
+        let within_sync_limit = char_count > 0 && char_count <= MAX_SYNC_CHARS as u32;
+        let within_long_form_limit =
+            char_count > 0 && char_count <= long_form::MAX_CHARS_PER_TASK as u32;
 
         Ok(ValidationResult {
-            is_valid,
+            is_valid: within_sync_limit,
             character_count: char_count,
             estimated_duration: Some(char_count as f32 * 0.05),
-            warnings: if char_count > 2500 {
+            warnings: if char_count > MAX_SYNC_CHARS as u32 * 5 / 6 && within_sync_limit {
                 vec!["Text approaching limit".to_string()]
             } else {
                 vec![]
             },
-            errors: if !is_valid {
+            errors: if within_sync_limit {
+                vec![]
+            } else if within_long_form_limit {
+                vec![format!(
+                    "Text exceeds the {}-character synchronous limit; use synthesize_long_form (up to {} characters) instead",
+                    MAX_SYNC_CHARS,
+                    long_form::MAX_CHARS_PER_TASK
+                )]
+            } else if char_count == 0 {
                 vec!["Text must be 1-3000 characters".to_string()]
             } else {
-                vec![]
+                vec![format!(
+                    "Text exceeds the long-form limit of {} characters",
+                    long_form::MAX_CHARS_PER_TASK
+                )]
             },
         })
     }
 }
 
 impl StreamingGuest for PollyComponent {
-    fn create_stream(_options: SynthesisOptions) -> Result<StreamSession, TtsError> {
-        Err(unsupported("Polly streaming not supported"))
+    fn create_stream(options: SynthesisOptions) -> Result<StreamSession, TtsError> {
+        info!(
+            "Polly: Creating client-side streaming session for voice {}",
+            options.voice_id
+        );
+        STREAM_MANAGER.with(|manager| manager.create_stream(options))
     }
 
-    fn stream_send_text(_session_id: String, _input: TextInput) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_send_text(session_id: String, input: TextInput) -> Result<(), TtsError> {
+        let client = Self::create_client()?;
+        let lexicon_names = LEXICON_NAMES.with(|cell| cell.borrow().clone());
+        STREAM_MANAGER
+            .with(|manager| manager.send_text(&client, &session_id, input, &lexicon_names))
     }
 
-    fn stream_finish(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_finish(session_id: String) -> Result<(), TtsError> {
+        let client = Self::create_client()?;
+        let lexicon_names = LEXICON_NAMES.with(|cell| cell.borrow().clone());
+        STREAM_MANAGER.with(|manager| manager.finish(&client, &session_id, &lexicon_names))
     }
 
-    fn stream_receive_chunk(_session_id: String) -> Result<Option<Vec<u8>>, TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_receive_chunk(session_id: String) -> Result<Option<AudioChunk>, TtsError> {
+        STREAM_MANAGER.with(|manager| manager.receive_chunk(&session_id))
     }
 
-    fn stream_has_pending(_session_id: String) -> Result<bool, TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_has_pending(session_id: String) -> Result<bool, TtsError> {
+        STREAM_MANAGER.with(|manager| manager.has_pending(&session_id))
     }
 
-    fn stream_get_status(_session_id: String) -> Result<StreamStatus, TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_get_status(session_id: String) -> Result<StreamStatus, TtsError> {
+        STREAM_MANAGER.with(|manager| manager.get_status(&session_id))
     }
 
-    fn stream_close(_session_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Streaming not supported"))
+    fn stream_close(session_id: String) -> Result<(), TtsError> {
+        STREAM_MANAGER.with(|manager| manager.close(&session_id))
     }
 }
 
@@ -208,43 +263,83 @@ impl AdvancedGuest for PollyComponent {
     }
 
     fn create_lexicon(
-        _name: String,
-        _language: String,
-        _entries: Option<Vec<PronunciationEntry>>,
+        name: String,
+        language: String,
+        entries: Option<Vec<PronunciationEntry>>,
     ) -> Result<String, TtsError> {
-        Err(unsupported("Lexicon management requires separate API"))
+        debug!("Polly: Creating lexicon '{}' ({})", name, language);
+        let entries = entries
+            .unwrap_or_default()
+            .into_iter()
+            .map(LexiconEntry::from)
+            .collect();
+        // Polly addresses lexicons by name, so the name doubles as the
+        // `lexicon_id` handed back to the caller.
+        let lexicon = Lexicon {
+            id: name.clone(),
+            name: name.clone(),
+            language,
+            entries,
+        };
+        let client = Self::create_client()?;
+        client.put_lexicon(name.clone(), lexicon.export_pls())?;
+        LEXICON_NAMES.with(|cell| cell.borrow_mut().push(name.clone()));
+        Ok(name)
     }
 
-    fn add_lexicon_entry(_lexicon_id: String, _entry: PronunciationEntry) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not implemented"))
+    fn add_lexicon_entry(lexicon_id: String, entry: PronunciationEntry) -> Result<(), TtsError> {
+        let client = Self::create_client()?;
+        let pls = client.get_lexicon(lexicon_id.clone())?;
+        let mut lexicon = advanced::parse_pls(&lexicon_id, &pls);
+        lexicon.add_entry(entry.into());
+        client.put_lexicon(lexicon_id, lexicon.export_pls())
     }
 
-    fn remove_lexicon_entry(_lexicon_id: String, _word: String) -> Result<(), TtsError> {
-        Err(unsupported("Lexicon not implemented"))
+    fn remove_lexicon_entry(lexicon_id: String, word: String) -> Result<(), TtsError> {
+        let client = Self::create_client()?;
+        let pls = client.get_lexicon(lexicon_id.clone())?;
+        let mut lexicon = advanced::parse_pls(&lexicon_id, &pls);
+        lexicon.remove_entry(&word);
+        client.put_lexicon(lexicon_id, lexicon.export_pls())
     }
 
-    fn export_lexicon(_lexicon_id: String) -> Result<String, TtsError> {
-        Err(unsupported("Lexicon not implemented"))
+    fn export_lexicon(lexicon_id: String) -> Result<String, TtsError> {
+        let client = Self::create_client()?;
+        client.get_lexicon(lexicon_id)
     }
 
     fn synthesize_long_form(
-        _content: String,
-        _voice_id: String,
-        _output_location: String,
-        _chapter_breaks: Option<Vec<u32>>,
+        content: String,
+        voice_id: String,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
     ) -> Result<LongFormJob, TtsError> {
-        Err(unsupported("Long-form synthesis not yet implemented"))
+        info!(
+            "Polly: Starting long-form synthesis of {} chars for voice {}",
+            content.len(),
+            voice_id
+        );
+        let client = Self::create_client()?;
+        LONG_FORM.with(|tracker| {
+            tracker.synthesize_long_form(&client, &content, &voice_id, output_location, chapter_breaks)
+        })
     }
 
-    fn get_long_form_status(_job_id: String) -> Result<LongFormResult, TtsError> {
-        Err(unsupported("Long-form not supported"))
+    fn get_long_form_status(job_id: String) -> Result<LongFormResult, TtsError> {
+        let client = Self::create_client()?;
+        LONG_FORM.with(|tracker| tracker.get_long_form_status(&client, &job_id))
     }
 
-    fn cancel_long_form(_job_id: String) -> Result<(), TtsError> {
-        Err(unsupported("Long-form not supported"))
+    fn cancel_long_form(job_id: String) -> Result<(), TtsError> {
+        LONG_FORM.with(|tracker| tracker.cancel_long_form(&job_id))
     }
 }
 
+impl AudioQueryGuest for PollyComponent {}
+
+impl VocabularyFilterGuest for PollyComponent {}
+impl DictionaryGuest for PollyComponent {}
+
 impl ExtendedGuest for PollyComponent {}
 
 type DurablePollyComponent = DurableTts<PollyComponent>;
@@ -0,0 +1,296 @@
+//! Long-form synthesis backed by Polly's own asynchronous task API
+//! (`StartSpeechSynthesisTask` / `GetSpeechSynthesisTask`) rather than
+//! client-side chunking: each chapter becomes its own S3-delivered task, and
+//! `get_long_form_status` simply re-polls AWS instead of tracking audio
+//! locally.
+//!
+//! `chapter_breaks` splits `content` into one task per chapter (sharing the
+//! bucket but writing under a per-chapter key prefix) so a caller gets a
+//! single job id covering every chapter. Starting a task is wrapped in
+//! [`start_task_durable`] so a worker restart replays the already-issued
+//! `TaskId`s instead of launching duplicate AWS tasks; polling status is a
+//! plain read and isn't persisted.
+use crate::client::PollyClient;
+use golem_tts::exports::golem::tts::advanced::{
+    LongFormJob as WitLongFormJob, LongFormResult as WitLongFormResult,
+};
+use golem_tts::golem::tts::types::TtsError as WitTtsError;
+use golem_tts::long_form::split_into_segments;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Longest chapter Polly will accept in a single synthesis task.
+pub(crate) const MAX_CHARS_PER_TASK: usize = 100_000;
+
+struct LongFormJobRecord {
+    task_ids: Vec<String>,
+    output_location: String,
+    cancelled: bool,
+}
+
+/// Registry mapping our job id to the underlying Polly task id(s), one
+/// instance per provider component behind its own `thread_local`.
+pub struct LongFormTracker {
+    jobs: RefCell<HashMap<String, LongFormJobRecord>>,
+}
+
+impl Default for LongFormTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LongFormTracker {
+    pub fn new() -> Self {
+        Self {
+            jobs: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn synthesize_long_form(
+        &self,
+        client: &PollyClient,
+        content: &str,
+        voice_id: &str,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
+    ) -> Result<WitLongFormJob, WitTtsError> {
+        let (bucket, prefix) = parse_s3_location(&output_location)?;
+        let chapters = split_into_segments(content, chapter_breaks.as_deref(), MAX_CHARS_PER_TASK);
+        let job_id = uuid::Uuid::new_v4().to_string();
+
+        let mut task_ids = Vec::with_capacity(chapters.len());
+        for (index, chapter) in chapters.iter().enumerate() {
+            let key_prefix = match &prefix {
+                Some(prefix) => format!("{}/chapter-{:04}", prefix, index),
+                None => format!("chapter-{:04}", index),
+            };
+            let task = start_task_durable(&job_id, index as u32, client, chapter, voice_id, &bucket, &key_prefix)?;
+            task_ids.push(task.task_id);
+        }
+        let total_segments = task_ids.len() as u32;
+
+        self.jobs.borrow_mut().insert(
+            job_id.clone(),
+            LongFormJobRecord {
+                task_ids,
+                output_location,
+                cancelled: false,
+            },
+        );
+
+        Ok(WitLongFormJob {
+            job_id,
+            status: "processing".to_string(),
+            total_segments,
+        })
+    }
+
+    /// Re-poll every task belonging to `job_id` via `GetSpeechSynthesisTask`
+    /// and fold their statuses into one aggregate result: `failed` if any
+    /// task failed, `completed` only once every task has, `processing`
+    /// otherwise.
+    pub fn get_long_form_status(
+        &self,
+        client: &PollyClient,
+        job_id: &str,
+    ) -> Result<WitLongFormResult, WitTtsError> {
+        let jobs = self.jobs.borrow();
+        let job = jobs
+            .get(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+
+        let mut completed = 0u32;
+        let mut failed_reason = None;
+        for task_id in &job.task_ids {
+            let task = client.get_synthesis_task(task_id)?;
+            match task.task_status.as_str() {
+                "completed" => completed += 1,
+                "failed" => {
+                    failed_reason = failed_reason
+                        .or(task.task_status_reason)
+                        .or_else(|| Some(format!("Polly task {} failed", task_id)))
+                }
+                _ => {}
+            }
+        }
+
+        let total_segments = job.task_ids.len() as u32;
+        let status = if job.cancelled {
+            "cancelled"
+        } else if failed_reason.is_some() {
+            "failed"
+        } else if completed == total_segments {
+            "completed"
+        } else {
+            "processing"
+        };
+
+        Ok(WitLongFormResult {
+            job_id: job_id.to_string(),
+            status: status.to_string(),
+            percent_complete: if total_segments == 0 {
+                100.0
+            } else {
+                (completed as f32 / total_segments as f32) * 100.0
+            },
+            segments_completed: completed,
+            total_segments,
+            output_location: job.output_location.clone(),
+            // Audio lands in S3, not inline; `output_location` is how a
+            // caller retrieves it once `status` is "completed".
+            audio_data: Vec::new(),
+            error: failed_reason,
+        })
+    }
+
+    /// Polly has no API to cancel an in-flight synthesis task, so this only
+    /// stops `get_long_form_status` from continuing to report progress.
+    pub fn cancel_long_form(&self, job_id: &str) -> Result<(), WitTtsError> {
+        let mut jobs = self.jobs.borrow_mut();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+        job.cancelled = true;
+        Ok(())
+    }
+}
+
+/// Parse `s3://bucket[/key-prefix]` into its bucket and optional key prefix.
+fn parse_s3_location(output_location: &str) -> Result<(String, Option<String>), WitTtsError> {
+    let rest = output_location.strip_prefix("s3://").ok_or_else(|| {
+        WitTtsError::InvalidConfiguration(format!(
+            "Polly long-form output_location must be an s3:// URI, got '{}'",
+            output_location
+        ))
+    })?;
+
+    match rest.split_once('/') {
+        Some((bucket, prefix)) if !prefix.is_empty() => {
+            Ok((bucket.to_string(), Some(prefix.trim_end_matches('/').to_string())))
+        }
+        _ => Ok((rest.trim_end_matches('/').to_string(), None)),
+    }
+}
+
+#[cfg(feature = "durability")]
+mod durable_start {
+    use super::{PollyClient, WitTtsError};
+    use golem_rust::bindings::golem::durability::durability::DurableFunctionType;
+    use golem_rust::durability::Durability;
+    use golem_rust::{with_persistence_level, FromValueAndType, IntoValue, PersistenceLevel};
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct StartSynthesisTaskInput {
+        job_id: String,
+        index: u32,
+        text: String,
+        voice_id: String,
+        s3_bucket: String,
+        s3_key_prefix: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    pub(super) struct StartedTask {
+        pub task_id: String,
+    }
+
+    pub(super) fn start_task_durable(
+        job_id: &str,
+        index: u32,
+        client: &PollyClient,
+        text: &str,
+        voice_id: &str,
+        s3_bucket: &str,
+        s3_key_prefix: &str,
+    ) -> Result<StartedTask, WitTtsError> {
+        let durability = Durability::<StartedTask, WitTtsError>::new(
+            "golem_tts",
+            "polly_start_synthesis_task",
+            DurableFunctionType::WriteRemote,
+        );
+
+        if durability.is_live() {
+            let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                client
+                    .start_synthesis_task(text, voice_id, s3_bucket, Some(s3_key_prefix))
+                    .map(|task| StartedTask { task_id: task.task_id })
+            });
+            durability.persist(
+                StartSynthesisTaskInput {
+                    job_id: job_id.to_string(),
+                    index,
+                    text: text.to_string(),
+                    voice_id: voice_id.to_string(),
+                    s3_bucket: s3_bucket.to_string(),
+                    s3_key_prefix: s3_key_prefix.to_string(),
+                },
+                result,
+            )
+        } else {
+            durability.replay()
+        }
+    }
+}
+
+#[cfg(feature = "durability")]
+use durable_start::{start_task_durable as start_task_durable_inner, StartedTask};
+
+#[cfg(feature = "durability")]
+fn start_task_durable(
+    job_id: &str,
+    index: u32,
+    client: &PollyClient,
+    text: &str,
+    voice_id: &str,
+    s3_bucket: &str,
+    s3_key_prefix: &str,
+) -> Result<crate::client::PollySynthesisTask, WitTtsError> {
+    let StartedTask { task_id } =
+        start_task_durable_inner(job_id, index, client, text, voice_id, s3_bucket, s3_key_prefix)?;
+    Ok(crate::client::PollySynthesisTask {
+        task_id,
+        task_status: "scheduled".to_string(),
+        task_status_reason: None,
+        output_uri: None,
+    })
+}
+
+#[cfg(not(feature = "durability"))]
+fn start_task_durable(
+    _job_id: &str,
+    _index: u32,
+    client: &PollyClient,
+    text: &str,
+    voice_id: &str,
+    s3_bucket: &str,
+    s3_key_prefix: &str,
+) -> Result<crate::client::PollySynthesisTask, WitTtsError> {
+    client.start_synthesis_task(text, voice_id, s3_bucket, Some(s3_key_prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bucket_and_prefix() {
+        assert_eq!(
+            parse_s3_location("s3://my-bucket/audio/book1").unwrap(),
+            ("my-bucket".to_string(), Some("audio/book1".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_bucket_only() {
+        assert_eq!(
+            parse_s3_location("s3://my-bucket").unwrap(),
+            ("my-bucket".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn rejects_non_s3_location() {
+        assert!(parse_s3_location("https://example.com/out").is_err());
+    }
+}
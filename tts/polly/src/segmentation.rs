@@ -0,0 +1,144 @@
+//! Sentence-boundary buffering for incremental streaming input.
+//!
+//! Accumulates incoming text and only commits a segment once it ends on a
+//! sentence boundary (`.`/`!`/`?`/newline followed by whitespace), so a
+//! caller that streams text token-by-token doesn't get its buffer split
+//! mid-abbreviation (`Dr. Smith`) or mid-decimal (`3.14`): a `.` that
+//! ends one of a fixed list of abbreviations is skipped, and the `.` in a
+//! decimal is not followed by whitespace, so neither is treated as a
+//! sentence end.
+
+const ABBREVIATIONS: &[&str] = &[
+    "Mr.", "Mrs.", "Ms.", "Dr.", "Prof.", "Jr.", "Sr.", "St.", "vs.", "etc.", "e.g.", "i.e.",
+];
+
+/// Accumulates streamed text and yields completed sentences as soon as
+/// the unflushed tail contains a boundary.
+pub struct SentenceSegmenter {
+    buffer: String,
+    committed_len: usize,
+}
+
+impl Default for SentenceSegmenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SentenceSegmenter {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            committed_len: 0,
+        }
+    }
+
+    /// Append newly received text and return zero or more sentences that
+    /// have now completed. The unflushed remainder stays buffered so it
+    /// can absorb more text before it's treated as a sentence end.
+    pub fn push(&mut self, content: &str) -> Vec<String> {
+        self.buffer.push_str(content);
+
+        let mut segments = Vec::new();
+        while let Some(end) = self.next_boundary() {
+            let segment_end = self.committed_len + end;
+            let segment = self.buffer[self.committed_len..segment_end].trim();
+            if !segment.is_empty() {
+                segments.push(segment.to_string());
+            }
+            self.committed_len = segment_end;
+        }
+        segments
+    }
+
+    /// Flush whatever remains in the buffer, regardless of boundaries.
+    /// Call once on `finish` to emit the final, necessarily-unterminated
+    /// segment.
+    pub fn flush_remaining(&mut self) -> Option<String> {
+        let tail = self.buffer[self.committed_len..].trim();
+        if tail.is_empty() {
+            return None;
+        }
+        let segment = tail.to_string();
+        self.committed_len = self.buffer.len();
+        Some(segment)
+    }
+
+    /// Find the end offset (exclusive, byte index relative to
+    /// `committed_len`) of the earliest completed sentence in the
+    /// unflushed tail, or `None` if nothing has completed yet.
+    fn next_boundary(&self) -> Option<usize> {
+        let tail = &self.buffer[self.committed_len..];
+
+        for (i, ch) in tail.char_indices() {
+            if !matches!(ch, '.' | '!' | '?' | '\n') {
+                continue;
+            }
+            if ch == '\n' {
+                return Some(i + 1);
+            }
+
+            // A streamed terminator followed by more non-whitespace text
+            // (e.g. the "14" in "3.14") isn't a sentence end yet; wait for
+            // either whitespace or a `finish`-triggered flush.
+            let followed_by_whitespace = tail[i + ch.len_utf8()..]
+                .chars()
+                .next()
+                .map(char::is_whitespace)
+                .unwrap_or(false);
+            if !followed_by_whitespace {
+                continue;
+            }
+
+            if ch == '.' && ends_with_abbreviation(&tail[..i + 1]) {
+                continue;
+            }
+
+            return Some(i + ch.len_utf8());
+        }
+
+        None
+    }
+}
+
+fn ends_with_abbreviation(prefix: &str) -> bool {
+    let trimmed = prefix.trim_end();
+    ABBREVIATIONS.iter().any(|abbr| trimmed.ends_with(abbr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_sentence_boundary() {
+        let mut seg = SentenceSegmenter::new();
+        let segments = seg.push("Hello there. How are you");
+        assert_eq!(segments, vec!["Hello there.".to_string()]);
+    }
+
+    #[test]
+    fn keeps_abbreviation_together() {
+        let mut seg = SentenceSegmenter::new();
+        let segments = seg.push("Dr. Smith arrived. ");
+        assert_eq!(segments, vec!["Dr. Smith arrived.".to_string()]);
+    }
+
+    #[test]
+    fn keeps_decimal_together() {
+        let mut seg = SentenceSegmenter::new();
+        let segments = seg.push("The rate is 3.14 percent. ");
+        assert_eq!(segments, vec!["The rate is 3.14 percent.".to_string()]);
+    }
+
+    #[test]
+    fn flush_remaining_emits_trailing_partial_sentence() {
+        let mut seg = SentenceSegmenter::new();
+        assert!(seg.push("no terminator yet").is_empty());
+        assert_eq!(
+            seg.flush_remaining(),
+            Some("no terminator yet".to_string())
+        );
+        assert_eq!(seg.flush_remaining(), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! Client-side incremental streaming for AWS Polly.
+//!
+//! Polly has no streaming synthesis endpoint, so `stream_send_text` buffers
+//! incoming text through a [`SentenceSegmenter`] and, as soon as a sentence
+//! completes, eagerly synthesizes it with the same `SynthesizeSpeech` call
+//! [`PollyClient::synthesize`] uses for one-shot requests. The resulting
+//! audio is queued per session so `stream_receive_chunk` drains it FIFO,
+//! in the order the text was spoken; `stream_has_pending` is true while
+//! audio is queued or until `stream_finish` has been called.
+use crate::client::PollyClient;
+use crate::segmentation::SentenceSegmenter;
+use golem_tts::exports::golem::tts::streaming::{
+    StreamSession as WitStreamSession, StreamStatus as WitStreamStatus,
+};
+use golem_tts::exports::golem::tts::synthesis::SynthesisOptions as WitSynthesisOptions;
+use golem_tts::golem::tts::types::{
+    AudioChunk as WitAudioChunk, TextInput as WitTextInput, TtsError as WitTtsError,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+
+struct StreamSessionData {
+    options: WitSynthesisOptions,
+    segmenter: RefCell<SentenceSegmenter>,
+    queued_audio: RefCell<VecDeque<Vec<u8>>>,
+    bytes_produced: Cell<u64>,
+    segments_produced: Cell<u32>,
+    finished: Cell<bool>,
+    error: RefCell<Option<String>>,
+}
+
+#[derive(Default)]
+pub struct StreamManager {
+    sessions: RefCell<HashMap<String, StreamSessionData>>,
+}
+
+impl StreamManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn create_stream(
+        &self,
+        options: WitSynthesisOptions,
+    ) -> Result<WitStreamSession, WitTtsError> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        // Segments go through `PollyClient::synthesize`, which always
+        // requests MP3.
+        let encoding = "mp3".to_string();
+        let sample_rate = options
+            .audio_config
+            .as_ref()
+            .and_then(|c| c.sample_rate)
+            .unwrap_or(24000);
+        let model = options.voice_id.clone();
+
+        self.sessions.borrow_mut().insert(
+            session_id.clone(),
+            StreamSessionData {
+                options,
+                segmenter: RefCell::new(SentenceSegmenter::new()),
+                queued_audio: RefCell::new(VecDeque::new()),
+                bytes_produced: Cell::new(0),
+                segments_produced: Cell::new(0),
+                finished: Cell::new(false),
+                error: RefCell::new(None),
+            },
+        );
+
+        Ok(WitStreamSession {
+            session_id,
+            model,
+            encoding,
+            sample_rate,
+        })
+    }
+
+    /// Feed `input` through the session's [`SentenceSegmenter`] and
+    /// synthesize any sentence that just completed.
+    pub fn send_text(
+        &self,
+        client: &PollyClient,
+        session_id: &str,
+        input: WitTextInput,
+        lexicon_names: &[String],
+    ) -> Result<(), WitTtsError> {
+        let segments = {
+            let sessions = self.sessions.borrow();
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+            session.segmenter.borrow_mut().push(&input.content)
+        };
+
+        for segment in segments {
+            let mut segment_input = input.clone();
+            segment_input.content = segment;
+            self.synthesize_segment(client, session_id, segment_input, lexicon_names)?;
+        }
+        Ok(())
+    }
+
+    /// Flush any trailing partial sentence and mark the session finished.
+    pub fn finish(
+        &self,
+        client: &PollyClient,
+        session_id: &str,
+        lexicon_names: &[String],
+    ) -> Result<(), WitTtsError> {
+        let tail = {
+            let sessions = self.sessions.borrow();
+            let session = sessions
+                .get(session_id)
+                .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+            session.segmenter.borrow_mut().flush_remaining()
+        };
+
+        if let Some(tail) = tail {
+            let input = WitTextInput {
+                content: tail,
+                text_type: golem_tts::golem::tts::types::TextType::Plain,
+            };
+            self.synthesize_segment(client, session_id, input, lexicon_names)?;
+        }
+
+        let sessions = self.sessions.borrow();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+        session.finished.set(true);
+        Ok(())
+    }
+
+    pub fn receive_chunk(&self, session_id: &str) -> Result<Option<WitAudioChunk>, WitTtsError> {
+        let sessions = self.sessions.borrow();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+
+        let popped = session.queued_audio.borrow_mut().pop_front();
+        Ok(popped.map(|data| WitAudioChunk {
+                data,
+                is_final: session.finished.get() && session.queued_audio.borrow().is_empty(),
+                sequence_number: 0,
+                timing_info: None,
+            }))
+    }
+
+    pub fn has_pending(&self, session_id: &str) -> Result<bool, WitTtsError> {
+        let sessions = self.sessions.borrow();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+
+        let has_queued_audio = !session.queued_audio.borrow().is_empty();
+        let has_unflushed_text = !session.finished.get();
+        Ok(has_queued_audio || has_unflushed_text)
+    }
+
+    /// `bytes_produced`/`segments_produced` aren't part of this WIT
+    /// struct's fixed shape, so they stay internal diagnostics rather
+    /// than surfacing here.
+    pub fn get_status(&self, session_id: &str) -> Result<WitStreamStatus, WitTtsError> {
+        let sessions = self.sessions.borrow();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+
+        let error = session.error.borrow().clone();
+        let is_active = !session.finished.get() && error.is_none();
+
+        Ok(WitStreamStatus {
+            status: if error.is_some() {
+                "error".to_string()
+            } else if is_active {
+                "active".to_string()
+            } else {
+                "finished".to_string()
+            },
+            is_active,
+            has_pending_chunks: !session.queued_audio.borrow().is_empty(),
+            error,
+        })
+    }
+
+    pub fn close(&self, session_id: &str) -> Result<(), WitTtsError> {
+        self.sessions.borrow_mut().remove(session_id);
+        Ok(())
+    }
+
+    fn synthesize_segment(
+        &self,
+        client: &PollyClient,
+        session_id: &str,
+        input: WitTextInput,
+        lexicon_names: &[String],
+    ) -> Result<(), WitTtsError> {
+        let sessions = self.sessions.borrow();
+        let session = sessions
+            .get(session_id)
+            .ok_or_else(|| WitTtsError::SessionNotFound(session_id.to_string()))?;
+
+        match client.synthesize(input, session.options.clone(), lexicon_names) {
+            Ok(result) => {
+                session
+                    .bytes_produced
+                    .set(session.bytes_produced.get() + result.audio_data.len() as u64);
+                session
+                    .segments_produced
+                    .set(session.segments_produced.get() + 1);
+                session.queued_audio.borrow_mut().push_back(result.audio_data);
+                Ok(())
+            }
+            Err(e) => {
+                *session.error.borrow_mut() = Some(format!("{:?}", e));
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! VOICEVOX-style editable audio query: a structured, inspectable prosody
+//! plan produced by `create_audio_query` and consumed by
+//! `synthesize_from_query`. Callers can adjust accent, mora length, and
+//! pitch on the returned [`AudioQuery`] before committing to audio, instead
+//! of only getting one-shot text-to-speech, and replaying a previously
+//! synthesized query reproduces the exact edited result.
+
+use golem_rust::{FromValueAndType, IntoValue};
+use serde::{Deserialize, Serialize};
+
+/// A single mora (the smallest rhythmic unit in Japanese prosody, roughly
+/// one "beat"): an optional leading consonant, a vowel, and a pitch (f0)
+/// value for each.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, IntoValue, FromValueAndType)]
+pub struct Mora {
+    pub text: String,
+    pub consonant: Option<String>,
+    pub consonant_length: Option<f32>,
+    pub vowel: String,
+    pub vowel_length: f32,
+    pub pitch: f32,
+}
+
+/// A run of [`Mora`]s sharing one pitch accent, plus `accent` — the
+/// 1-indexed mora where pitch drops (0 means the phrase has no accent
+/// nucleus) — and an optional trailing pause.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, IntoValue, FromValueAndType)]
+pub struct AccentPhrase {
+    pub moras: Vec<Mora>,
+    pub accent: u32,
+    pub pause_mora: Option<Mora>,
+    pub is_interrogative: bool,
+}
+
+/// A structured, editable prosody plan for one synthesis request. Callers
+/// get one back from `create_audio_query`, can mutate individual
+/// `accent_phrases` (shift the accent, lengthen a mora, flatten
+/// intonation), then hand the edited query to `synthesize_from_query` for
+/// deterministic, reproducible audio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, IntoValue, FromValueAndType)]
+pub struct AudioQuery {
+    pub accent_phrases: Vec<AccentPhrase>,
+    pub speed_scale: f32,
+    pub pitch_scale: f32,
+    pub intonation_scale: f32,
+    pub pre_phoneme_length: f32,
+    pub post_phoneme_length: f32,
+}
+
+impl Default for AudioQuery {
+    fn default() -> Self {
+        Self {
+            accent_phrases: Vec::new(),
+            speed_scale: 1.0,
+            pitch_scale: 0.0,
+            intonation_scale: 1.0,
+            pre_phoneme_length: 0.1,
+            post_phoneme_length: 0.1,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 
+use crate::audio_query::AudioQuery;
 use crate::exports::golem::tts::advanced::Guest as WitAdvancedGuest;
 use crate::exports::golem::tts::advanced::Guest as TtsAdvancedGuest;
 use crate::exports::golem::tts::streaming::Guest as WitStreamingGuest;
@@ -8,13 +9,25 @@ use crate::exports::golem::tts::synthesis::Guest as WitSynthesisGuest;
 use crate::exports::golem::tts::synthesis::Guest as TtsSynthesisGuest;
 use crate::exports::golem::tts::voices::Guest as WitVoicesGuest;
 use crate::exports::golem::tts::voices::Guest as TtsVoicesGuest;
+use crate::guest::AudioQueryGuest;
+use crate::guest::DictionaryGuest;
+use crate::guest::VocabularyFilterGuest;
+use crate::lexicon::DictionaryFormat;
+use crate::vocab_filter::{FilterMode, MatchSpan};
 
 pub struct DurableTts<Impl> {
     phantom: PhantomData<Impl>,
 }
 
 pub trait ExtendedGuest:
-    TtsVoicesGuest + TtsSynthesisGuest + TtsStreamingGuest + TtsAdvancedGuest + 'static
+    TtsVoicesGuest
+    + TtsSynthesisGuest
+    + TtsStreamingGuest
+    + TtsAdvancedGuest
+    + AudioQueryGuest
+    + VocabularyFilterGuest
+    + DictionaryGuest
+    + 'static
 {
 }
 
@@ -221,6 +234,51 @@ mod passthrough_impl {
             Impl::cancel_long_form(job_id)
         }
     }
+
+    impl<Impl: ExtendedGuest> DurableTts<Impl> {
+        pub fn create_audio_query(
+            input: WitTextInput,
+            voice_id: String,
+        ) -> Result<AudioQuery, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::create_audio_query(input, voice_id)
+        }
+
+        pub fn synthesize_from_query(
+            query: AudioQuery,
+            voice_id: String,
+        ) -> Result<WitSynthesisResult, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::synthesize_from_query(query, voice_id)
+        }
+
+        pub fn create_vocabulary_filter(
+            language: String,
+            phrases: Vec<String>,
+        ) -> Result<String, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::create_vocabulary_filter(language, phrases)
+        }
+
+        pub fn apply_vocabulary_filter(
+            input: WitTextInput,
+            filter_id: String,
+            mode: FilterMode,
+        ) -> Result<(WitTextInput, Vec<MatchSpan>), WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::apply_vocabulary_filter(input, filter_id, mode)
+        }
+
+        pub fn import_lexicon(
+            name: String,
+            language: String,
+            format: DictionaryFormat,
+            data: String,
+        ) -> Result<String, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::import_lexicon(name, language, format, data)
+        }
+    }
 }
 
 #[cfg(feature = "durability")]
@@ -341,37 +399,163 @@ mod durable_impl {
     impl<Impl: ExtendedGuest> WitStreamingGuest for DurableTts<Impl> {
         fn create_stream(options: WitSynthesisOptions) -> Result<WitStreamSession, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::create_stream(options)
+
+            let durability = Durability::<WitStreamSession, WitTtsError>::new(
+                "golem_tts",
+                "create_stream",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let is_live = durability.is_live();
+            let session = if is_live {
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::create_stream(options.clone())
+                });
+                durability.persist(CreateStreamInput { options: options.clone() }, result)
+            } else {
+                durability.replay()
+            }?;
+
+            streaming_state::remember_options(&session.session_id, options);
+            if is_live {
+                streaming_state::remember_live_session(&session.session_id, session.session_id.clone());
+            }
+
+            Ok(session)
         }
 
         fn stream_send_text(session_id: String, input: WitTextInput) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_send_text(session_id, input)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "stream_send_text",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_send_text(live_id, input.clone())
+                });
+                durability.persist(
+                    StreamSendTextInput {
+                        session_id: session_id.clone(),
+                        input: input.clone(),
+                    },
+                    result,
+                )
+            } else {
+                durability.replay()
+            };
+
+            if result.is_ok() {
+                streaming_state::remember_sent_text(&session_id, input);
+            }
+            result
         }
 
         fn stream_finish(session_id: String) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_finish(session_id)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "stream_finish",
+                DurableFunctionType::WriteRemote,
+            );
+
+            if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_finish(live_id)
+                });
+                durability.persist(StreamSessionInput { session_id }, result)
+            } else {
+                durability.replay()
+            }
         }
 
         fn stream_receive_chunk(session_id: String) -> Result<Option<WitAudioChunk>, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_receive_chunk(session_id)
+
+            let durability = Durability::<Option<WitAudioChunk>, WitTtsError>::new(
+                "golem_tts",
+                "stream_receive_chunk",
+                DurableFunctionType::WriteRemote,
+            );
+
+            if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_receive_chunk(live_id)
+                });
+                durability.persist(StreamSessionInput { session_id }, result)
+            } else {
+                durability.replay()
+            }
         }
 
         fn stream_has_pending(session_id: String) -> Result<bool, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_has_pending(session_id)
+
+            let durability = Durability::<bool, WitTtsError>::new(
+                "golem_tts",
+                "stream_has_pending",
+                DurableFunctionType::WriteRemote,
+            );
+
+            if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_has_pending(live_id)
+                });
+                durability.persist(StreamSessionInput { session_id }, result)
+            } else {
+                durability.replay()
+            }
         }
 
         fn stream_get_status(session_id: String) -> Result<WitStreamStatus, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_get_status(session_id)
+
+            let durability = Durability::<WitStreamStatus, WitTtsError>::new(
+                "golem_tts",
+                "stream_get_status",
+                DurableFunctionType::WriteRemote,
+            );
+
+            if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_get_status(live_id)
+                });
+                durability.persist(StreamSessionInput { session_id }, result)
+            } else {
+                durability.replay()
+            }
         }
 
         fn stream_close(session_id: String) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::stream_close(session_id)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "stream_close",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let live_id = streaming_state::live_session_id::<Impl>(&session_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_close(live_id)
+                });
+                durability.persist(StreamSessionInput { session_id: session_id.clone() }, result)
+            } else {
+                durability.replay()
+            };
+
+            streaming_state::forget(&session_id);
+            result
         }
     }
 
@@ -417,7 +601,41 @@ mod durable_impl {
             entries: Option<Vec<WitPronunciationEntry>>,
         ) -> Result<String, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::create_lexicon(name, language, entries)
+
+            let durability = Durability::<String, WitTtsError>::new(
+                "golem_tts",
+                "create_lexicon",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let is_live = durability.is_live();
+            let lexicon_id = if is_live {
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::create_lexicon(name.clone(), language.clone(), entries.clone())
+                });
+                durability.persist(
+                    CreateLexiconInput {
+                        name: name.clone(),
+                        language: language.clone(),
+                        entries: entries.clone(),
+                    },
+                    result,
+                )
+            } else {
+                durability.replay()
+            }?;
+
+            lexicon_state::remember_create(
+                &lexicon_id,
+                name,
+                language,
+                entries.unwrap_or_default(),
+            );
+            if is_live {
+                lexicon_state::remember_live_lexicon(&lexicon_id, lexicon_id.clone());
+            }
+
+            Ok(lexicon_id)
         }
 
         fn add_lexicon_entry(
@@ -425,17 +643,70 @@ mod durable_impl {
             entry: WitPronunciationEntry,
         ) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::add_lexicon_entry(lexicon_id, entry)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "add_lexicon_entry",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let live_id = lexicon_state::live_lexicon_id::<Impl>(&lexicon_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::add_lexicon_entry(live_id, entry.clone())
+                });
+                durability.persist(
+                    AddLexiconEntryInput {
+                        lexicon_id: lexicon_id.clone(),
+                        entry: entry.clone(),
+                    },
+                    result,
+                )
+            } else {
+                durability.replay()
+            };
+
+            if result.is_ok() {
+                lexicon_state::remember_add(&lexicon_id, entry);
+            }
+            result
         }
 
         fn remove_lexicon_entry(lexicon_id: String, word: String) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::remove_lexicon_entry(lexicon_id, word)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "remove_lexicon_entry",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let live_id = lexicon_state::live_lexicon_id::<Impl>(&lexicon_id)?;
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::remove_lexicon_entry(live_id, word.clone())
+                });
+                durability.persist(
+                    RemoveLexiconEntryInput {
+                        lexicon_id: lexicon_id.clone(),
+                        word: word.clone(),
+                    },
+                    result,
+                )
+            } else {
+                durability.replay()
+            };
+
+            if result.is_ok() {
+                lexicon_state::remember_remove(&lexicon_id, word);
+            }
+            result
         }
 
         fn export_lexicon(lexicon_id: String) -> Result<String, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::export_lexicon(lexicon_id)
+            let live_id = lexicon_state::live_lexicon_id::<Impl>(&lexicon_id)?;
+            Impl::export_lexicon(live_id)
         }
 
         fn synthesize_long_form(
@@ -445,17 +716,163 @@ mod durable_impl {
             chapter_breaks: Option<Vec<u32>>,
         ) -> Result<WitLongFormJob, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::synthesize_long_form(content, voice_id, output_location, chapter_breaks)
+
+            let durability = Durability::<WitLongFormJob, WitTtsError>::new(
+                "golem_tts",
+                "synthesize_long_form",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let job = if durability.is_live() {
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::synthesize_long_form(
+                        content.clone(),
+                        voice_id.clone(),
+                        output_location.clone(),
+                        chapter_breaks.clone(),
+                    )
+                });
+                durability.persist(
+                    SynthesizeLongFormInput {
+                        content: content.clone(),
+                        voice_id: voice_id.clone(),
+                        output_location: output_location.clone(),
+                        chapter_breaks: chapter_breaks.clone(),
+                    },
+                    result,
+                )
+            } else {
+                durability.replay()
+            }?;
+
+            long_form_state::remember_request(
+                &job.job_id,
+                content,
+                voice_id,
+                output_location,
+                chapter_breaks.unwrap_or_default(),
+            );
+            Ok(job)
         }
 
         fn get_long_form_status(job_id: String) -> Result<WitLongFormResult, WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::get_long_form_status(job_id)
+
+            let durability = Durability::<WitLongFormResult, WitTtsError>::new(
+                "golem_tts",
+                "get_long_form_status",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let provider_id = long_form_state::provider_job_id(&job_id);
+                let mut polled = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::get_long_form_status(provider_id)
+                });
+
+                // The provider's own job registry lives in a thread-local and
+                // doesn't survive a crash; a `NotFound` here means the worker
+                // was replayed and lost it. Resume from the last chapter
+                // confirmed written rather than surfacing the error.
+                if matches!(polled, Err(WitTtsError::NotFound(_))) {
+                    long_form_state::resume::<Impl>(&job_id)?;
+                    let provider_id = long_form_state::provider_job_id(&job_id);
+                    polled = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                        Impl::get_long_form_status(provider_id)
+                    });
+                }
+
+                durability.persist(GetLongFormStatusInput { job_id: job_id.clone() }, polled)
+            } else {
+                durability.replay()
+            };
+
+            if let Ok(status) = &result {
+                long_form_state::remember_progress(&job_id, status.percent_complete);
+            }
+            result
         }
 
         fn cancel_long_form(job_id: String) -> Result<(), WitTtsError> {
             LOGGING_STATE.with_borrow_mut(|state| state.init());
-            Impl::cancel_long_form(job_id)
+
+            let durability = Durability::<(), WitTtsError>::new(
+                "golem_tts",
+                "cancel_long_form",
+                DurableFunctionType::WriteRemote,
+            );
+
+            let result = if durability.is_live() {
+                let provider_id = long_form_state::provider_job_id(&job_id);
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::cancel_long_form(provider_id)
+                });
+                durability.persist(CancelLongFormInput { job_id: job_id.clone() }, result)
+            } else {
+                durability.replay()
+            };
+
+            long_form_state::forget(&job_id);
+            result
+        }
+    }
+
+    impl<Impl: ExtendedGuest> DurableTts<Impl> {
+        pub fn create_audio_query(
+            input: WitTextInput,
+            voice_id: String,
+        ) -> Result<AudioQuery, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::create_audio_query(input, voice_id)
+        }
+
+        pub fn synthesize_from_query(
+            query: AudioQuery,
+            voice_id: String,
+        ) -> Result<WitSynthesisResult, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+
+            let durability = Durability::<WitSynthesisResult, WitTtsError>::new(
+                "golem_tts",
+                "synthesize_from_query",
+                DurableFunctionType::WriteRemote,
+            );
+
+            if durability.is_live() {
+                let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::synthesize_from_query(query.clone(), voice_id.clone())
+                });
+                durability.persist(SynthesizeFromQueryInput { query, voice_id }, result)
+            } else {
+                durability.replay()
+            }
+        }
+
+        pub fn create_vocabulary_filter(
+            language: String,
+            phrases: Vec<String>,
+        ) -> Result<String, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::create_vocabulary_filter(language, phrases)
+        }
+
+        pub fn apply_vocabulary_filter(
+            input: WitTextInput,
+            filter_id: String,
+            mode: FilterMode,
+        ) -> Result<(WitTextInput, Vec<MatchSpan>), WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::apply_vocabulary_filter(input, filter_id, mode)
+        }
+
+        pub fn import_lexicon(
+            name: String,
+            language: String,
+            format: DictionaryFormat,
+            data: String,
+        ) -> Result<String, WitTtsError> {
+            LOGGING_STATE.with_borrow_mut(|state| state.init());
+            Impl::import_lexicon(name, language, format, data)
         }
     }
 
@@ -465,15 +882,367 @@ mod durable_impl {
         options: WitSynthesisOptions,
     }
 
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct SynthesizeLongFormInput {
+        content: String,
+        voice_id: String,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct GetLongFormStatusInput {
+        job_id: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct CancelLongFormInput {
+        job_id: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct SynthesizeFromQueryInput {
+        query: AudioQuery,
+        voice_id: String,
+    }
+
     #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
     struct SynthesizeBatchInput {
         inputs: Vec<WitTextInput>,
         options: WitSynthesisOptions,
     }
 
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct CreateLexiconInput {
+        name: String,
+        language: String,
+        entries: Option<Vec<WitPronunciationEntry>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct AddLexiconEntryInput {
+        lexicon_id: String,
+        entry: WitPronunciationEntry,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct RemoveLexiconEntryInput {
+        lexicon_id: String,
+        word: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct CreateStreamInput {
+        options: WitSynthesisOptions,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct StreamSendTextInput {
+        session_id: String,
+        input: WitTextInput,
+    }
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct StreamSessionInput {
+        session_id: String,
+    }
+
     impl From<&WitTtsError> for WitTtsError {
         fn from(error: &WitTtsError) -> Self {
             error.clone()
         }
     }
+
+    /// Tracks, per stable (replayed) stream session id, everything needed to
+    /// recreate the provider-side session after a crash: the options it was
+    /// created with, the live provider session id once one exists, and the
+    /// ordered text already sent so a freshly recreated session can be
+    /// fast-forwarded past sends that happened before the crash.
+    mod streaming_state {
+        use super::{
+            with_persistence_level, ExtendedGuest, PersistenceLevel, TtsStreamingGuest,
+            WitSynthesisOptions, WitTextInput, WitTtsError,
+        };
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            static OPTIONS: RefCell<HashMap<String, WitSynthesisOptions>> = RefCell::new(HashMap::new());
+            static LIVE_SESSION: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+            static SENT_TEXT: RefCell<HashMap<String, Vec<WitTextInput>>> = RefCell::new(HashMap::new());
+        }
+
+        pub(super) fn remember_options(session_id: &str, options: WitSynthesisOptions) {
+            OPTIONS.with_borrow_mut(|m| m.insert(session_id.to_string(), options));
+        }
+
+        pub(super) fn remember_live_session(session_id: &str, live_session_id: String) {
+            LIVE_SESSION.with_borrow_mut(|m| m.insert(session_id.to_string(), live_session_id));
+        }
+
+        pub(super) fn remember_sent_text(session_id: &str, input: WitTextInput) {
+            SENT_TEXT.with_borrow_mut(|m| m.entry(session_id.to_string()).or_default().push(input));
+        }
+
+        pub(super) fn forget(session_id: &str) {
+            OPTIONS.with_borrow_mut(|m| m.remove(session_id));
+            LIVE_SESSION.with_borrow_mut(|m| m.remove(session_id));
+            SENT_TEXT.with_borrow_mut(|m| m.remove(session_id));
+        }
+
+        /// Resolve `session_id` (the id callers and the oplog know about) to
+        /// the id `Impl` actually recognizes, recreating the provider-side
+        /// session and replaying its prior `stream_send_text` calls (without
+        /// persisting them again) the first time this is needed after a
+        /// crash recreated the worker with fresh, empty provider state.
+        pub(super) fn live_session_id<Impl: ExtendedGuest>(
+            session_id: &str,
+        ) -> Result<String, WitTtsError> {
+            if let Some(live_id) = LIVE_SESSION.with_borrow(|m| m.get(session_id).cloned()) {
+                return Ok(live_id);
+            }
+
+            let options = OPTIONS.with_borrow(|m| m.get(session_id).cloned()).ok_or_else(|| {
+                WitTtsError::SessionNotFound(format!(
+                    "No durable record of stream session {session_id}"
+                ))
+            })?;
+
+            let recreated = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                Impl::create_stream(options)
+            })?;
+
+            let sent = SENT_TEXT.with_borrow(|m| m.get(session_id).cloned().unwrap_or_default());
+            for input in sent {
+                with_persistence_level(PersistenceLevel::PersistNothing, || {
+                    Impl::stream_send_text(recreated.session_id.clone(), input)
+                })?;
+            }
+
+            LIVE_SESSION
+                .with_borrow_mut(|m| m.insert(session_id.to_string(), recreated.session_id.clone()));
+            Ok(recreated.session_id)
+        }
+    }
+
+    /// Tracks, per stable (replayed) long-form job id, everything needed to
+    /// resume a job whose provider-side registry was lost across a crash:
+    /// the original request (including the resolved `chapter_breaks`) and
+    /// the highest confirmed char offset ever observed for it.
+    mod long_form_state {
+        use super::{
+            with_persistence_level, ExtendedGuest, PersistenceLevel, TtsAdvancedGuest,
+            WitTtsError,
+        };
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        #[derive(Clone)]
+        struct JobRequest {
+            content: String,
+            voice_id: String,
+            output_location: String,
+            chapter_breaks: Vec<u32>,
+        }
+
+        thread_local! {
+            static REQUESTS: RefCell<HashMap<String, JobRequest>> = RefCell::new(HashMap::new());
+            static CONFIRMED_OFFSET: RefCell<HashMap<String, u32>> = RefCell::new(HashMap::new());
+            static PROVIDER_JOB: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+        }
+
+        pub(super) fn remember_request(
+            job_id: &str,
+            content: String,
+            voice_id: String,
+            output_location: String,
+            chapter_breaks: Vec<u32>,
+        ) {
+            REQUESTS.with_borrow_mut(|m| {
+                m.insert(
+                    job_id.to_string(),
+                    JobRequest { content, voice_id, output_location, chapter_breaks },
+                )
+            });
+        }
+
+        /// Record the highest char offset into the job's content ever
+        /// confirmed complete, so a resume after a crash only re-renders
+        /// chapters that weren't already written to `output_location`.
+        ///
+        /// `percent_complete` is the provider's fraction of *segments*
+        /// done, not chapters — a chapter is split into several segments
+        /// by the provider's own chunker (see e.g. `split_into_segments`),
+        /// so segment count can't be used directly as a `chapter_breaks`
+        /// index. Instead, scale it by the job's total content length to
+        /// estimate a char offset, which `resume` then rounds down to the
+        /// nearest chapter break to avoid skipping unconfirmed content.
+        pub(super) fn remember_progress(job_id: &str, percent_complete: f32) {
+            let content_chars =
+                REQUESTS.with_borrow(|m| m.get(job_id).map(|r| r.content.chars().count()));
+            let Some(content_chars) = content_chars else {
+                return;
+            };
+
+            let estimated_offset =
+                ((percent_complete.clamp(0.0, 100.0) / 100.0) * content_chars as f32) as u32;
+
+            CONFIRMED_OFFSET.with_borrow_mut(|m| {
+                let confirmed = m.entry(job_id.to_string()).or_insert(0);
+                *confirmed = (*confirmed).max(estimated_offset);
+            });
+        }
+
+        pub(super) fn forget(job_id: &str) {
+            REQUESTS.with_borrow_mut(|m| m.remove(job_id));
+            CONFIRMED_OFFSET.with_borrow_mut(|m| m.remove(job_id));
+            PROVIDER_JOB.with_borrow_mut(|m| m.remove(job_id));
+        }
+
+        /// The id to query `Impl` with: the id of a job resumed via
+        /// [`resume`] if one exists, otherwise the stable `job_id` itself.
+        pub(super) fn provider_job_id(job_id: &str) -> String {
+            PROVIDER_JOB
+                .with_borrow(|m| m.get(job_id).cloned())
+                .unwrap_or_else(|| job_id.to_string())
+        }
+
+        /// Re-issue synthesis for the chapters at or after the last one
+        /// confirmed written, so a provider that lost its in-memory job
+        /// registry across a crash resumes the render instead of restarting
+        /// a multi-hour book from its first chapter.
+        pub(super) fn resume<Impl: ExtendedGuest>(job_id: &str) -> Result<(), WitTtsError> {
+            let request = REQUESTS.with_borrow(|m| m.get(job_id).cloned()).ok_or_else(|| {
+                WitTtsError::NotFound(format!("No durable record of long-form job {job_id}"))
+            })?;
+            let confirmed_offset =
+                CONFIRMED_OFFSET.with_borrow(|m| m.get(job_id).copied().unwrap_or(0));
+
+            // Resume from the latest chapter break at or before the last
+            // confirmed offset rather than the offset itself, so an estimate
+            // that lands mid-chapter never skips the unconfirmed remainder
+            // of that chapter.
+            let start_offset = request
+                .chapter_breaks
+                .iter()
+                .rev()
+                .find(|&&offset| offset <= confirmed_offset)
+                .copied()
+                .unwrap_or(0) as usize;
+            let remaining_content: String = request.content.chars().skip(start_offset).collect();
+            let remaining_breaks: Vec<u32> = request
+                .chapter_breaks
+                .iter()
+                .filter(|&&offset| offset as usize > start_offset)
+                .map(|offset| offset - start_offset as u32)
+                .collect();
+            let chapter_breaks = if remaining_breaks.is_empty() { None } else { Some(remaining_breaks) };
+
+            let resumed = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                Impl::synthesize_long_form(
+                    remaining_content,
+                    request.voice_id.clone(),
+                    request.output_location.clone(),
+                    chapter_breaks,
+                )
+            })?;
+
+            PROVIDER_JOB.with_borrow_mut(|m| m.insert(job_id.to_string(), resumed.job_id));
+            Ok(())
+        }
+    }
+
+    /// Tracks, per stable (replayed) lexicon id, everything needed to
+    /// rebuild a lexicon whose provider-side store was lost across a
+    /// crash: the original `create_lexicon` request, every mutation
+    /// applied since, and the live provider-side lexicon id once one
+    /// exists.
+    mod lexicon_state {
+        use super::{
+            with_persistence_level, ExtendedGuest, PersistenceLevel, TtsAdvancedGuest,
+            WitPronunciationEntry, WitTtsError,
+        };
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        #[derive(Clone)]
+        enum Mutation {
+            Add(WitPronunciationEntry),
+            Remove(String),
+        }
+
+        #[derive(Clone)]
+        struct LexiconRequest {
+            name: String,
+            language: String,
+            entries: Vec<WitPronunciationEntry>,
+        }
+
+        thread_local! {
+            static REQUESTS: RefCell<HashMap<String, LexiconRequest>> = RefCell::new(HashMap::new());
+            static MUTATIONS: RefCell<HashMap<String, Vec<Mutation>>> = RefCell::new(HashMap::new());
+            static LIVE_LEXICON: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+        }
+
+        pub(super) fn remember_create(
+            lexicon_id: &str,
+            name: String,
+            language: String,
+            entries: Vec<WitPronunciationEntry>,
+        ) {
+            REQUESTS.with_borrow_mut(|m| {
+                m.insert(lexicon_id.to_string(), LexiconRequest { name, language, entries })
+            });
+        }
+
+        pub(super) fn remember_live_lexicon(lexicon_id: &str, live_lexicon_id: String) {
+            LIVE_LEXICON.with_borrow_mut(|m| m.insert(lexicon_id.to_string(), live_lexicon_id));
+        }
+
+        pub(super) fn remember_add(lexicon_id: &str, entry: WitPronunciationEntry) {
+            MUTATIONS
+                .with_borrow_mut(|m| m.entry(lexicon_id.to_string()).or_default().push(Mutation::Add(entry)));
+        }
+
+        pub(super) fn remember_remove(lexicon_id: &str, word: String) {
+            MUTATIONS
+                .with_borrow_mut(|m| m.entry(lexicon_id.to_string()).or_default().push(Mutation::Remove(word)));
+        }
+
+        /// Resolve `lexicon_id` (the id callers and the oplog know about) to
+        /// the id `Impl` actually recognizes, recreating the provider-side
+        /// lexicon and replaying its prior mutations (without persisting
+        /// them again) the first time this is needed after a crash
+        /// recreated the worker with fresh, empty provider state.
+        pub(super) fn live_lexicon_id<Impl: ExtendedGuest>(
+            lexicon_id: &str,
+        ) -> Result<String, WitTtsError> {
+            if let Some(live_id) = LIVE_LEXICON.with_borrow(|m| m.get(lexicon_id).cloned()) {
+                return Ok(live_id);
+            }
+
+            let request = REQUESTS.with_borrow(|m| m.get(lexicon_id).cloned()).ok_or_else(|| {
+                WitTtsError::NotFound(format!("No durable record of lexicon {lexicon_id}"))
+            })?;
+
+            let recreated_id = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                Impl::create_lexicon(
+                    request.name.clone(),
+                    request.language.clone(),
+                    Some(request.entries.clone()),
+                )
+            })?;
+
+            let mutations = MUTATIONS.with_borrow(|m| m.get(lexicon_id).cloned().unwrap_or_default());
+            for mutation in mutations {
+                with_persistence_level(PersistenceLevel::PersistNothing, || match mutation {
+                    Mutation::Add(entry) => Impl::add_lexicon_entry(recreated_id.clone(), entry),
+                    Mutation::Remove(word) => Impl::remove_lexicon_entry(recreated_id.clone(), word),
+                })?;
+            }
+
+            LIVE_LEXICON.with_borrow_mut(|m| m.insert(lexicon_id.to_string(), recreated_id.clone()));
+            Ok(recreated_id)
+        }
+    }
 }
@@ -1,5 +1,6 @@
 use crate::exports::golem::tts::types::TtsError as WitTtsError;
 use derive_more::From;
+use std::time::Duration;
 
 #[derive(Debug, From)]
 pub enum Error {
@@ -10,6 +11,10 @@ pub enum Error {
     Utf8(std::string::FromUtf8Error),
     IoError(std::io::Error),
     WitTts(WitTtsError),
+    /// The request did not complete within the client's configured
+    /// `request_timeout`.
+    #[from(ignore)]
+    Timeout(Duration),
 }
 
 impl From<Error> for WitTtsError {
@@ -22,6 +27,9 @@ impl From<Error> for WitTtsError {
             Error::Utf8(e) => WitTtsError::InternalError(format!("UTF-8 error: {}", e)),
             Error::IoError(e) => WitTtsError::InternalError(format!("IO error: {}", e)),
             Error::WitTts(e) => e,
+            Error::Timeout(d) => {
+                WitTtsError::NetworkError(format!("Request timed out after {:?}", d))
+            }
         }
     }
 }
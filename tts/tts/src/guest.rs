@@ -1,3 +1,4 @@
+use crate::audio_query::AudioQuery;
 use crate::exports::golem::tts::advanced::{
     AudioSample as WitAudioSample, Guest as WitAdvancedGuest, LongFormJob as WitLongFormJob,
     LongFormResult as WitLongFormResult, PronunciationEntry as WitPronunciationEntry,
@@ -19,6 +20,8 @@ use crate::golem::tts::types::{
     AudioChunk as WitAudioChunk, SynthesisResult as WitSynthesisResult, TextInput as WitTextInput,
     TimingInfo as WitTimingInfo,
 };
+use crate::lexicon::DictionaryFormat;
+use crate::vocab_filter::{FilterMode, MatchSpan};
 
 /// Trait for voice management operations
 pub trait TtsVoicesGuest {
@@ -51,6 +54,79 @@ pub trait TtsSynthesisGuest {
     ) -> Result<WitValidationResult, WitTtsError>;
 }
 
+/// Optional VOICEVOX-style editable-query workflow, alongside
+/// [`TtsSynthesisGuest`]'s one-shot `synthesize`: `create_audio_query`
+/// produces a structured [`AudioQuery`] the caller can edit (shift accent,
+/// lengthen a mora, flatten intonation), then `synthesize_from_query`
+/// renders the (possibly edited) query to audio. Providers that have
+/// nothing like VOICEVOX's mora-level prosody model inherit the default
+/// `UnsupportedOperation` response instead of needing empty overrides.
+pub trait AudioQueryGuest {
+    fn create_audio_query(
+        _input: WitTextInput,
+        _voice_id: String,
+    ) -> Result<AudioQuery, WitTtsError> {
+        Err(WitTtsError::UnsupportedOperation(
+            "create_audio_query is not supported by this provider".to_string(),
+        ))
+    }
+
+    fn synthesize_from_query(
+        _query: AudioQuery,
+        _voice_id: String,
+    ) -> Result<WitSynthesisResult, WitTtsError> {
+        Err(WitTtsError::UnsupportedOperation(
+            "synthesize_from_query is not supported by this provider".to_string(),
+        ))
+    }
+}
+
+/// Optional text-sanitization extension: register a filter vocabulary (a
+/// word/phrase list per language, sharing the lexicon id space) and run it
+/// over a [`WitTextInput`] before synthesis, masking, removing, or tagging
+/// matches depending on the caller's `mode`. Providers with nothing like
+/// this inherit the default `UnsupportedOperation` response instead of
+/// needing empty overrides.
+pub trait VocabularyFilterGuest {
+    fn create_vocabulary_filter(
+        _language: String,
+        _phrases: Vec<String>,
+    ) -> Result<String, WitTtsError> {
+        Err(WitTtsError::UnsupportedOperation(
+            "create_vocabulary_filter is not supported by this provider".to_string(),
+        ))
+    }
+
+    fn apply_vocabulary_filter(
+        _input: WitTextInput,
+        _filter_id: String,
+        _mode: FilterMode,
+    ) -> Result<(WitTextInput, Vec<MatchSpan>), WitTtsError> {
+        Err(WitTtsError::UnsupportedOperation(
+            "apply_vocabulary_filter is not supported by this provider".to_string(),
+        ))
+    }
+}
+
+/// Optional standard-pronunciation-dictionary import, alongside
+/// [`TtsAdvancedGuest::export_lexicon`]: [`crate::lexicon::Lexicon::import`]
+/// does the parsing/validation, this just exposes it as a provider
+/// operation so a lexicon exported from one provider (or hand-authored as
+/// CSV/JSON) can be migrated into another. Providers inherit the default
+/// `UnsupportedOperation` response instead of needing an empty override.
+pub trait DictionaryGuest {
+    fn import_lexicon(
+        _name: String,
+        _language: String,
+        _format: DictionaryFormat,
+        _data: String,
+    ) -> Result<String, WitTtsError> {
+        Err(WitTtsError::UnsupportedOperation(
+            "import_lexicon is not supported by this provider".to_string(),
+        ))
+    }
+}
+
 /// Trait for streaming operations
 pub trait TtsStreamingGuest {
     fn create_stream(options: WitSynthesisOptions) -> Result<WitStreamSession, WitTtsError>;
@@ -1,16 +1,100 @@
 use crate::error::Error;
 use bytes::Bytes;
 use log::trace;
+use std::time::Duration;
 use wstd::http::{Client, Request};
 use wstd::io::AsyncRead;
 use wstd::runtime::block_on;
 
-/// HTTP client using wstd (WASI) backend  
-pub struct WstdHttpClient;
+/// Retryable HTTP statuses: rate limiting and the transient 5xx family.
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 502 | 503 | 504)
+}
+
+/// Parse a `Retry-After` header value, which is either delta-seconds
+/// (`"120"`) or an HTTP-date (`"Wed, 21 Oct 2015 07:28:00 GMT"`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value.trim()).ok()?;
+    let now = std::time::SystemTime::now();
+    target.duration_since(now).ok()
+}
+
+/// Full-jitter a computed backoff delay down to a uniform random value in
+/// `[0, delay]`, so concurrent retriers desynchronize instead of waking in
+/// lockstep. Only appropriate when `delay` is our own guess, not a
+/// server-mandated wait — see `apply_jitter_floor` for `Retry-After`.
+fn apply_jitter(delay: Duration, jitter: bool) -> Duration {
+    if !jitter || delay.is_zero() {
+        return delay;
+    }
+    use rand::Rng;
+    let millis = rand::thread_rng().gen_range(0..=delay.as_millis() as u64);
+    Duration::from_millis(millis)
+}
+
+/// Desynchronize concurrent retriers without ever sleeping less than
+/// `floor`: a server's `Retry-After` is a mandated minimum wait, not a
+/// hint, so jitter may only add headroom on top of it, never shorten it
+/// the way full-jitter does for a computed backoff delay.
+fn apply_jitter_floor(floor: Duration, jitter: bool) -> Duration {
+    if !jitter || floor.is_zero() {
+        return floor;
+    }
+    use rand::Rng;
+    let headroom_ms = (floor.as_millis() as u64 / 4).max(1);
+    let extra = rand::thread_rng().gen_range(0..=headroom_ms);
+    floor + Duration::from_millis(extra)
+}
+
+/// Retry policy applied by [`WstdHttpClient::execute`]: exponential backoff
+/// with jitter on transport errors and retryable HTTP statuses, honoring a
+/// server's `Retry-After` header over the computed delay when present.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub backoff_multiplier: f64,
+    pub request_timeout: Duration,
+    /// Apply jitter to retry delays so concurrent clients desynchronize
+    /// instead of retrying in lockstep. A `Retry-After` wait is never
+    /// shortened by it (see `apply_jitter_floor`); only a computed backoff
+    /// delay is.
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(30),
+            backoff_multiplier: 2.0,
+            request_timeout: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+/// HTTP client using wstd (WASI) backend
+pub struct WstdHttpClient {
+    retry_config: RetryConfig,
+}
 
 impl WstdHttpClient {
     pub fn new() -> Self {
-        Self
+        Self {
+            retry_config: RetryConfig::default(),
+        }
+    }
+
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
     }
 
     pub fn get(&self, url: &str) -> RequestBuilder {
@@ -40,7 +124,74 @@ impl WstdHttpClient {
         }
     }
 
+    pub fn put(&self, url: &str) -> RequestBuilder {
+        RequestBuilder {
+            method: http::Method::PUT,
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
     pub fn execute(&self, builder: RequestBuilder) -> Result<Response, Error> {
+        let mut delay = self.retry_config.initial_delay;
+        let max_retries = self.retry_config.max_retries;
+
+        for attempt in 0..=max_retries {
+            match self.execute_once(builder.clone()) {
+                Ok(response) => {
+                    if !is_retryable_status(response.status) || attempt >= max_retries {
+                        return Ok(response);
+                    }
+
+                    trace!(
+                        "HTTP {} returned {}, waiting before retry",
+                        builder.url,
+                        response.status
+                    );
+                    let retry_after = response
+                        .headers
+                        .iter()
+                        .find(|(k, _)| k.eq_ignore_ascii_case("retry-after"))
+                        .and_then(|(_, v)| parse_retry_after(v));
+
+                    let wait = match retry_after {
+                        Some(d) => {
+                            let floor = std::cmp::min(d, self.retry_config.max_delay);
+                            apply_jitter_floor(floor, self.retry_config.jitter)
+                        }
+                        None => apply_jitter(delay, self.retry_config.jitter),
+                    };
+                    std::thread::sleep(wait);
+                    delay = std::cmp::min(
+                        Duration::from_millis(
+                            (delay.as_millis() as f64 * self.retry_config.backoff_multiplier)
+                                as u64,
+                        ),
+                        self.retry_config.max_delay,
+                    );
+                }
+                Err(e) => {
+                    if attempt >= max_retries {
+                        return Err(e);
+                    }
+                    trace!("HTTP {} failed ({:?}), waiting before retry", builder.url, e);
+                    std::thread::sleep(apply_jitter(delay, self.retry_config.jitter));
+                    delay = std::cmp::min(
+                        Duration::from_millis(
+                            (delay.as_millis() as f64 * self.retry_config.backoff_multiplier)
+                                as u64,
+                        ),
+                        self.retry_config.max_delay,
+                    );
+                }
+            }
+        }
+
+        unreachable!("loop always returns by the final attempt")
+    }
+
+    fn execute_once(&self, builder: RequestBuilder) -> Result<Response, Error> {
         trace!("HTTP {} {}", builder.method, builder.url);
 
         // Build http::Request
@@ -48,6 +199,7 @@ impl WstdHttpClient {
             http::Method::GET => Request::get(&builder.url),
             http::Method::POST => Request::post(&builder.url),
             http::Method::DELETE => Request::delete(&builder.url),
+            http::Method::PUT => Request::put(&builder.url),
             _ => {
                 return Err(Error::IoError(std::io::Error::new(
                     std::io::ErrorKind::InvalidInput,
@@ -65,18 +217,24 @@ impl WstdHttpClient {
         let body_data = builder.body.unwrap_or_default();
         let http_req = req_builder.body(BodyWrapper::new(body_data))?;
 
-        // Use Client to send the request
+        // Use Client to send the request, bounding the whole round-trip
+        // (connect + headers + body) by the configured request timeout.
         let client = Client::new();
-        let mut response = block_on(async { client.send(http_req).await })?;
+        let timeout = self.retry_config.request_timeout;
+        let (status, headers, body) = block_on(async {
+            let send_and_read = async {
+                let mut response = client.send(http_req).await?;
+                let status = response.status();
+                let headers = response.headers().clone();
+                let mut buf = Vec::new();
+                response.body_mut().read_to_end(&mut buf).await?;
+                Ok::<_, wstd::http::Error>((status, headers, buf))
+            };
 
-        let status = response.status();
-        let headers = response.headers().clone();
-
-        // Read body bytes from IncomingBody
-        let body = block_on(async {
-            let mut buf = Vec::new();
-            response.body_mut().read_to_end(&mut buf).await?;
-            Ok::<Vec<u8>, wstd::http::Error>(buf)
+            match wstd::time::timeout(timeout, send_and_read).await {
+                Ok(result) => result.map_err(Error::from),
+                Err(_) => Err(Error::Timeout(timeout)),
+            }
         })?;
 
         trace!("HTTP response status: {}", status);
@@ -97,6 +255,7 @@ impl WstdHttpClient {
     }
 }
 
+#[derive(Clone)]
 pub struct RequestBuilder {
     method: http::Method,
     url: String,
@@ -123,11 +282,134 @@ impl RequestBuilder {
         self
     }
 
+    /// Serialize `form` as a `multipart/form-data` body and set the
+    /// matching `Content-Type` header with its generated boundary.
+    pub fn multipart(mut self, form: MultipartForm) -> Self {
+        let boundary = form.boundary.clone();
+        self.body = Some(form.into_bytes());
+        self.headers.push((
+            "content-type".to_string(),
+            format!("multipart/form-data; boundary={}", boundary),
+        ));
+        self
+    }
+
     pub fn send(self) -> Result<Response, Error> {
         WstdHttpClient::new().execute(self)
     }
 }
 
+/// A single part of a `multipart/form-data` body.
+enum MultipartPart {
+    Text { name: String, value: String },
+    File {
+        name: String,
+        filename: String,
+        content_type: String,
+        data: Vec<u8>,
+    },
+}
+
+/// Builder for `multipart/form-data` request bodies: text fields plus
+/// named file parts with a filename and optional content type.
+pub struct MultipartForm {
+    boundary: String,
+    parts: Vec<MultipartPart>,
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self {
+            boundary: format!("----golem-boundary-{}", uuid::Uuid::new_v4()),
+            parts: Vec::new(),
+        }
+    }
+
+    pub fn add_text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push(MultipartPart::Text {
+            name: name.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    pub fn add_file(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.parts.push(MultipartPart::File {
+            name: name.into(),
+            filename: filename.into(),
+            content_type: "application/octet-stream".to_string(),
+            data: data.into(),
+        });
+        self
+    }
+
+    pub fn add_file_with_content_type(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        data: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.parts.push(MultipartPart::File {
+            name: name.into(),
+            filename: filename.into(),
+            content_type: content_type.into(),
+            data: data.into(),
+        });
+        self
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        let mut body = Vec::new();
+
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            match part {
+                MultipartPart::Text { name, value } => {
+                    body.extend_from_slice(
+                        format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", name)
+                            .as_bytes(),
+                    );
+                    body.extend_from_slice(value.as_bytes());
+                }
+                MultipartPart::File {
+                    name,
+                    filename,
+                    content_type,
+                    data,
+                } => {
+                    body.extend_from_slice(
+                        format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                            name, filename
+                        )
+                        .as_bytes(),
+                    );
+                    body.extend_from_slice(
+                        format!("Content-Type: {}\r\n\r\n", content_type).as_bytes(),
+                    );
+                    body.extend_from_slice(data);
+                }
+            }
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        body
+    }
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct Response {
     pub status: u16,
     pub headers: Vec<(String, String)>,
@@ -0,0 +1,149 @@
+//! Minimal BCP-47 language tag parsing and matching, shared by provider
+//! conversions so voice filtering isn't based on raw, inconsistently-cased
+//! strings like "en" vs "en-US".
+use std::fmt;
+
+/// A parsed BCP-47 language tag: primary language subtag, optional script
+/// and region. Good enough for the subset of tags TTS providers hand back
+/// (e.g. `en`, `en-US`, `en-Latn-GB`) without pulling in a full CLDR-backed
+/// implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageIdentifier {
+    pub language: String,
+    pub script: Option<String>,
+    pub region: Option<String>,
+}
+
+impl LanguageIdentifier {
+    pub fn parse(tag: &str) -> Self {
+        let mut subtags = tag.split(['-', '_']).filter(|s| !s.is_empty());
+        let language = subtags
+            .next()
+            .map(|s| s.to_lowercase())
+            .unwrap_or_else(|| "und".to_string());
+
+        let mut script = None;
+        let mut region = None;
+        for subtag in subtags {
+            if subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()) {
+                script = Some(format!(
+                    "{}{}",
+                    subtag[..1].to_uppercase(),
+                    subtag[1..].to_lowercase()
+                ));
+            } else if (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+            {
+                region = Some(subtag.to_uppercase());
+            }
+        }
+
+        Self {
+            language,
+            script,
+            region,
+        }
+    }
+
+    /// Language-only identifier, e.g. `en` from `en-GB`.
+    pub fn language_only(&self) -> LanguageIdentifier {
+        LanguageIdentifier {
+            language: self.language.clone(),
+            script: None,
+            region: None,
+        }
+    }
+
+    /// Yes/no match used for voice filtering: a neutral ("und"/empty)
+    /// requested tag matches anything; otherwise the languages must be
+    /// equal, and a requested region must equal this tag's region, while a
+    /// language-only request matches any region. Matches are not ranked, a
+    /// region mismatch does not fall back to a language-only match, and the
+    /// script subtag is not compared.
+    pub fn matches(&self, requested: &LanguageIdentifier) -> bool {
+        if requested.language == "und" || requested.language.is_empty() {
+            return true;
+        }
+        if self.language != requested.language {
+            return false;
+        }
+        match (&requested.region, &self.region) {
+            (Some(requested_region), Some(own_region)) => requested_region == own_region,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+/// English name for a handful of common ISO 639-1 language subtags, for
+/// providers that only expose the bare tag (e.g. `en`, `fr`) in their voice
+/// catalogs. Falls back to the tag itself when it isn't in the table.
+pub fn language_display_name(language: &str) -> String {
+    match language.to_lowercase().as_str() {
+        "en" => "English",
+        "es" => "Spanish",
+        "fr" => "French",
+        "de" => "German",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "nl" => "Dutch",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "zh" => "Chinese",
+        "ru" => "Russian",
+        "ar" => "Arabic",
+        "hi" => "Hindi",
+        "pl" => "Polish",
+        "sv" => "Swedish",
+        "tr" => "Turkish",
+        other => return other.to_string(),
+    }
+    .to_string()
+}
+
+impl fmt::Display for LanguageIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        if let Some(script) = &self.script {
+            write!(f, "-{}", script)?;
+        }
+        if let Some(region) = &self.region {
+            write!(f, "-{}", region)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_language_and_region() {
+        let id = LanguageIdentifier::parse("en-GB");
+        assert_eq!(id.language, "en");
+        assert_eq!(id.region.as_deref(), Some("GB"));
+    }
+
+    #[test]
+    fn exact_match_beats_language_only() {
+        let requested = LanguageIdentifier::parse("en-GB");
+        let british = LanguageIdentifier::parse("en-GB");
+        let american = LanguageIdentifier::parse("en-US");
+        assert!(british.matches(&requested));
+        assert!(!american.matches(&requested));
+    }
+
+    #[test]
+    fn language_only_request_matches_any_region() {
+        let requested = LanguageIdentifier::parse("en");
+        let british = LanguageIdentifier::parse("en-GB");
+        assert!(british.matches(&requested));
+    }
+
+    #[test]
+    fn display_name_falls_back_to_the_tag_itself() {
+        assert_eq!(language_display_name("en"), "English");
+        assert_eq!(language_display_name("xx"), "xx");
+    }
+}
@@ -0,0 +1,734 @@
+//! Client-side pronunciation lexicon/substitution subsystem.
+//!
+//! Providers that don't offer a server-side lexicon API (or whose lexicon
+//! API this crate hasn't wired up yet) can use a [`Lexicon`] to rewrite
+//! words to a preferred spelling or phonetic transcription before the text
+//! reaches `synthesize`/`synthesize_batch`.
+
+use crate::exports::golem::tts::advanced::PronunciationEntry as WitPronunciationEntry;
+use crate::exports::golem::tts::types::TtsError as WitTtsError;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How a lexicon entry's `word` is matched against input text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchMode {
+    /// Match the exact word, case-sensitive, on word boundaries.
+    ExactWord,
+    /// Match the word on word boundaries, ignoring case.
+    CaseInsensitive,
+    /// Treat `word` as a regular expression.
+    Regex,
+}
+
+/// A single word/phrase substitution, optionally carrying the same
+/// accent/mora metadata as a user pronunciation dictionary entry so
+/// [`Lexicon::export`]/[`Lexicon::import`] can round-trip dictionaries
+/// between providers without losing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LexiconEntry {
+    pub word: String,
+    pub replacement: String,
+    pub phonetic: Option<String>,
+    pub match_mode: MatchMode,
+    /// 1-indexed mora in `phonetic` where pitch drops; `None` means no
+    /// accent nucleus (a flat phrase).
+    pub accent: Option<u32>,
+    /// Part-of-speech/word-type hint, e.g. `"noun"` or `"proper-noun"`.
+    pub word_type: Option<String>,
+    /// Priority for resolving overlaps between entries matching the same
+    /// text, carried through [`Lexicon::export`]/[`Lexicon::import`].
+    /// [`Lexicon::apply`] does not read it: entries are applied in list
+    /// order.
+    pub priority: Option<u32>,
+}
+
+impl From<WitPronunciationEntry> for LexiconEntry {
+    fn from(entry: WitPronunciationEntry) -> Self {
+        Self {
+            word: entry.word,
+            replacement: entry.phonetic.clone(),
+            phonetic: Some(entry.phonetic),
+            match_mode: MatchMode::CaseInsensitive,
+            accent: None,
+            word_type: None,
+            priority: None,
+        }
+    }
+}
+
+/// A named, language-scoped set of [`LexiconEntry`] substitutions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lexicon {
+    pub id: String,
+    pub name: String,
+    pub language: String,
+    pub entries: Vec<LexiconEntry>,
+}
+
+impl Lexicon {
+    pub fn new(name: String, language: String, entries: Vec<LexiconEntry>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            language,
+            entries,
+        }
+    }
+
+    pub fn add_entry(&mut self, entry: LexiconEntry) {
+        self.entries.retain(|e| e.word != entry.word);
+        self.entries.push(entry);
+    }
+
+    pub fn remove_entry(&mut self, word: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.word != word);
+        self.entries.len() != before
+    }
+
+    /// Apply every entry in turn, rewriting `text` into its substituted form.
+    ///
+    /// When `supports_ssml` is `true` and an entry has a `phonetic`
+    /// transcription, the match is wrapped in an SSML `<phoneme>` tag instead
+    /// of being replaced by its plain-text `replacement`/alias.
+    pub fn apply(&self, text: &str, supports_ssml: bool) -> String {
+        let mut result = text.to_string();
+        for entry in &self.entries {
+            result = apply_entry(&result, entry, supports_ssml);
+        }
+        result
+    }
+
+    /// Serialize this lexicon to a portable JSON document.
+    pub fn export_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Serialize this lexicon to Pronunciation Lexicon Specification (PLS)
+    /// XML, the format AWS Polly's `put_lexicon` consumes.
+    pub fn export_pls(&self) -> String {
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<lexicon version=\"1.0\" xmlns=\"http://www.w3.org/2005/01/pronunciation-lexicon\" alphabet=\"ipa\" xml:lang=\"{}\">\n",
+            xml_escape(&self.language)
+        ));
+        for entry in &self.entries {
+            xml.push_str("  <lexeme>\n");
+            xml.push_str(&format!(
+                "    <grapheme>{}</grapheme>\n",
+                xml_escape(&entry.word)
+            ));
+            if let Some(phonetic) = &entry.phonetic {
+                xml.push_str(&format!(
+                    "    <phoneme>{}</phoneme>\n",
+                    xml_escape(phonetic)
+                ));
+            } else {
+                xml.push_str(&format!(
+                    "    <alias>{}</alias>\n",
+                    xml_escape(&entry.replacement)
+                ));
+            }
+            xml.push_str("  </lexeme>\n");
+        }
+        xml.push_str("</lexicon>\n");
+        xml
+    }
+
+    /// Export this lexicon's accent/mora metadata as a standard
+    /// pronunciation dictionary in `format`, so it can be migrated to
+    /// another provider. This is distinct from [`Self::export_json`]: that
+    /// round-trips the whole [`Lexicon`] (id, name, match modes, aliases),
+    /// while this exports just the portable word/reading/accent/priority
+    /// quadruple a pronunciation dictionary carries. See
+    /// [`Self::import`] for the inverse.
+    pub fn export(&self, format: DictionaryFormat) -> Result<String, WitTtsError> {
+        let rows: Vec<DictionaryRow> = self
+            .entries
+            .iter()
+            .map(|entry| DictionaryRow {
+                word: entry.word.clone(),
+                reading: entry.phonetic.clone().unwrap_or_default(),
+                accent: entry.accent.unwrap_or(0),
+                priority: entry.priority.unwrap_or(0),
+            })
+            .collect();
+
+        match format {
+            DictionaryFormat::Json => serde_json::to_string_pretty(&rows).map_err(|e| {
+                WitTtsError::InternalError(format!("Failed to export dictionary: {e}"))
+            }),
+            DictionaryFormat::Csv => {
+                let mut csv = String::from("word,reading,accent,priority\n");
+                for row in rows {
+                    csv.push_str(&format!(
+                        "{},{},{},{}\n",
+                        quote_csv_field(&row.word),
+                        quote_csv_field(&row.reading),
+                        row.accent,
+                        row.priority
+                    ));
+                }
+                Ok(csv)
+            }
+        }
+    }
+
+    /// Import a standard pronunciation dictionary in `format` as a new
+    /// [`Lexicon`] named `name`/`language`. Every row is validated before
+    /// any is accepted: `reading` must use only characters legal for
+    /// `language`, and `accent` (the mora where pitch drops) may not exceed
+    /// the reading's mora count. Invalid rows are collected and reported
+    /// together so a caller can fix the whole file in one pass.
+    pub fn import(
+        name: String,
+        language: String,
+        format: DictionaryFormat,
+        data: &str,
+    ) -> Result<Lexicon, WitTtsError> {
+        let rows = parse_dictionary_rows(format, data)?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        let mut errors = Vec::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            match validate_dictionary_row(&language, &row) {
+                Ok(()) => entries.push(LexiconEntry {
+                    word: row.word.clone(),
+                    replacement: row.word,
+                    phonetic: Some(row.reading),
+                    match_mode: MatchMode::CaseInsensitive,
+                    accent: Some(row.accent),
+                    word_type: None,
+                    priority: Some(row.priority),
+                }),
+                Err(reason) => errors.push(format!("row {}: {reason}", index + 1)),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(WitTtsError::InvalidInput(format!(
+                "Invalid pronunciation dictionary rows:\n{}",
+                errors.join("\n")
+            )));
+        }
+
+        Ok(Lexicon::new(name, language, entries))
+    }
+}
+
+/// Interchange format for [`Lexicon::export`]/[`Lexicon::import`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DictionaryFormat {
+    Json,
+    Csv,
+}
+
+/// The portable word/reading/accent/priority quadruple a standard
+/// pronunciation dictionary carries — the subset of [`LexiconEntry`] that
+/// survives a round trip through [`DictionaryFormat::Csv`]. `replacement`
+/// and `match_mode` don't appear here: a pronunciation dictionary describes
+/// how a word sounds, not what it's rewritten to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionaryRow {
+    pub word: String,
+    pub reading: String,
+    pub accent: u32,
+    pub priority: u32,
+}
+
+fn parse_dictionary_rows(
+    format: DictionaryFormat,
+    data: &str,
+) -> Result<Vec<DictionaryRow>, WitTtsError> {
+    match format {
+        DictionaryFormat::Json => serde_json::from_str(data)
+            .map_err(|e| WitTtsError::InvalidInput(format!("Malformed dictionary JSON: {e}"))),
+        DictionaryFormat::Csv => split_csv_records(data)
+            .into_iter()
+            .map(|record| record.trim().to_string())
+            .filter(|record| !record.is_empty())
+            .filter(|record| !record.eq_ignore_ascii_case("word,reading,accent,priority"))
+            .map(|record| {
+                let fields = split_csv_fields(&record);
+                let [word, reading, accent, priority]: [String; 4] =
+                    fields.try_into().map_err(|fields: Vec<String>| {
+                        WitTtsError::InvalidInput(format!(
+                            "Malformed CSV row '{record}': expected word,reading,accent,priority, got {} field(s)",
+                            fields.len()
+                        ))
+                    })?;
+                let accent: u32 = accent.parse().map_err(|_| {
+                    WitTtsError::InvalidInput(format!(
+                        "Malformed CSV row '{record}': accent must be a non-negative integer"
+                    ))
+                })?;
+                let priority: u32 = priority.parse().map_err(|_| {
+                    WitTtsError::InvalidInput(format!(
+                        "Malformed CSV row '{record}': priority must be a non-negative integer"
+                    ))
+                })?;
+                Ok(DictionaryRow { word, reading, accent, priority })
+            })
+            .collect(),
+    }
+}
+
+/// Quote `field` per RFC 4180 if it contains a comma, double quote, or
+/// newline, any of which would otherwise be misread as column/row
+/// structure by [`split_csv_records`]/[`split_csv_fields`] on import.
+/// Embedded double quotes are escaped by doubling, per the same RFC.
+fn quote_csv_field(field: &str) -> String {
+    if field.contains(['"', ',', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Undo [`quote_csv_field`]: strip a surrounding pair of double quotes and
+/// un-double any escaped `""` inside them. A field that was never quoted
+/// passes through unchanged.
+fn unquote_csv_field(field: &str) -> String {
+    match field.strip_prefix('"').and_then(|f| f.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => field.to_string(),
+    }
+}
+
+/// Split `data` into CSV records (rows), honoring RFC 4180 quoting so a
+/// quoted field's embedded newlines don't end the record early.
+fn split_csv_records(data: &str) -> Vec<String> {
+    let mut records = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                current.push('"');
+                if in_quotes && chars.peek() == Some(&'"') {
+                    current.push(chars.next().unwrap());
+                } else {
+                    in_quotes = !in_quotes;
+                }
+            }
+            '\n' if !in_quotes => records.push(std::mem::take(&mut current)),
+            '\r' if !in_quotes => {} // a following '\n' (if any) ends the record
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        records.push(current);
+    }
+    records
+}
+
+/// Split one CSV record into its fields, honoring RFC 4180 quoting so a
+/// quoted field's embedded commas don't split it, then unquote each field.
+fn split_csv_fields(record: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                current.push('"');
+                if in_quotes && chars.peek() == Some(&'"') {
+                    current.push(chars.next().unwrap());
+                } else {
+                    in_quotes = !in_quotes;
+                }
+            }
+            ',' if !in_quotes => {
+                fields.push(unquote_csv_field(current.trim()));
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    fields.push(unquote_csv_field(current.trim()));
+    fields
+}
+
+/// `reading` must use only characters legal for `language` (hiragana,
+/// katakana, and the long-vowel mark for Japanese; letters and common IPA
+/// punctuation otherwise), and `accent` may not exceed the reading's mora
+/// count.
+fn validate_dictionary_row(language: &str, row: &DictionaryRow) -> Result<(), String> {
+    if row.reading.is_empty() {
+        return Err(format!("'{}' has an empty reading", row.word));
+    }
+    if let Some(bad) = row
+        .reading
+        .chars()
+        .find(|c| !is_legal_reading_char(language, *c))
+    {
+        return Err(format!(
+            "'{}' reading '{}' contains '{}', not a legal symbol for language '{}'",
+            row.word, row.reading, bad, language
+        ));
+    }
+    let moras = mora_count(language, &row.reading);
+    if row.accent > moras {
+        return Err(format!(
+            "'{}' accent position {} exceeds its reading's mora count ({moras})",
+            row.word, row.accent
+        ));
+    }
+    Ok(())
+}
+
+fn is_legal_reading_char(language: &str, c: char) -> bool {
+    if language.starts_with("ja") {
+        matches!(c, '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' | 'ー' | '・')
+    } else {
+        c.is_alphabetic() || c.is_whitespace() || matches!(c, '.' | '-' | '\'' | 'ˈ' | 'ˌ' | 'ː')
+    }
+}
+
+/// Mora count used to bound `accent`: for Japanese, every kana counts as
+/// one mora except the small kana (ゃゅょ etc.) that attach to the
+/// preceding one; for other languages, each non-whitespace character in
+/// the reading is treated as one mora-equivalent unit.
+fn mora_count(language: &str, reading: &str) -> u32 {
+    if language.starts_with("ja") {
+        reading
+            .chars()
+            .filter(|c| !matches!(c, 'ゃ' | 'ゅ' | 'ょ' | 'ャ' | 'ュ' | 'ョ'))
+            .count() as u32
+    } else {
+        reading.chars().filter(|c| !c.is_whitespace()).count() as u32
+    }
+}
+
+fn apply_entry(text: &str, entry: &LexiconEntry, supports_ssml: bool) -> String {
+    let substitution = match (&entry.phonetic, supports_ssml) {
+        (Some(phonetic), true) => format!(
+            "<phoneme alphabet=\"ipa\" ph=\"{}\">{}</phoneme>",
+            xml_escape(phonetic),
+            entry.word
+        ),
+        _ => entry.replacement.clone(),
+    };
+    match entry.match_mode {
+        MatchMode::ExactWord => replace_word(text, &entry.word, &substitution, false),
+        MatchMode::CaseInsensitive => replace_word(text, &entry.word, &substitution, true),
+        MatchMode::Regex => Regex::new(&entry.word)
+            .map(|re| re.replace_all(text, substitution.as_str()).into_owned())
+            .unwrap_or_else(|_| text.to_string()),
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn replace_word(text: &str, word: &str, replacement: &str, ignore_case: bool) -> String {
+    let pattern = format!(r"\b{}\b", regex::escape(word));
+    let re = if ignore_case {
+        Regex::new(&format!("(?i){}", pattern))
+    } else {
+        Regex::new(&pattern)
+    };
+    match re {
+        Ok(re) => re.replace_all(text, replacement).into_owned(),
+        Err(_) => text.to_string(),
+    }
+}
+
+/// Apply every lexicon in `lexicons`, in order, to `text`.
+pub fn apply_all(lexicons: &[Lexicon], text: &str, supports_ssml: bool) -> String {
+    lexicons
+        .iter()
+        .fold(text.to_string(), |acc, lex| lex.apply(&acc, supports_ssml))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_word_replacement_respects_word_boundaries() {
+        let lex = Lexicon::new(
+            "test".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: "AWS".to_string(),
+                replacement: "Amazon Web Services".to_string(),
+                phonetic: None,
+                match_mode: MatchMode::ExactWord,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        assert_eq!(
+            lex.apply("Deploy to AWS today", false),
+            "Deploy to Amazon Web Services today"
+        );
+        assert_eq!(lex.apply("AWSome", false), "AWSome");
+    }
+
+    #[test]
+    fn case_insensitive_mode_ignores_case() {
+        let lex = Lexicon::new(
+            "test".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: "sql".to_string(),
+                replacement: "sequel".to_string(),
+                phonetic: None,
+                match_mode: MatchMode::CaseInsensitive,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        assert_eq!(lex.apply("I write SQL queries", false), "I write sequel queries");
+    }
+
+    #[test]
+    fn regex_mode_substitutes_pattern_matches() {
+        let lex = Lexicon::new(
+            "test".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: r"\d{3}-\d{4}".to_string(),
+                replacement: "a phone number".to_string(),
+                phonetic: None,
+                match_mode: MatchMode::Regex,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        assert_eq!(lex.apply("Call 555-1234 now", false), "Call a phone number now");
+    }
+
+    #[test]
+    fn ssml_support_wraps_phonetic_entries_in_phoneme_tags() {
+        let lex = Lexicon::new(
+            "test".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: "tomato".to_string(),
+                replacement: "tomayto".to_string(),
+                phonetic: Some("təˈmeɪtoʊ".to_string()),
+                match_mode: MatchMode::CaseInsensitive,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        assert_eq!(
+            lex.apply("Pass the tomato", true),
+            "Pass the <phoneme alphabet=\"ipa\" ph=\"təˈmeɪtoʊ\">tomato</phoneme>"
+        );
+        assert_eq!(lex.apply("Pass the tomato", false), "Pass the tomayto");
+    }
+
+    #[test]
+    fn export_pls_emits_grapheme_and_phoneme_or_alias() {
+        let lex = Lexicon::new(
+            "test".to_string(),
+            "en-US".to_string(),
+            vec![
+                LexiconEntry {
+                    word: "tomato".to_string(),
+                    replacement: "tomayto".to_string(),
+                    phonetic: Some("təˈmeɪtoʊ".to_string()),
+                    match_mode: MatchMode::CaseInsensitive,
+                    accent: None,
+                    word_type: None,
+                    priority: None,
+                },
+                LexiconEntry {
+                    word: "AWS".to_string(),
+                    replacement: "Amazon Web Services".to_string(),
+                    phonetic: None,
+                    match_mode: MatchMode::ExactWord,
+                    accent: None,
+                    word_type: None,
+                    priority: None,
+                },
+            ],
+        );
+        let pls = lex.export_pls();
+        assert!(pls.contains("<lexicon version=\"1.0\""));
+        assert!(pls.contains("<grapheme>tomato</grapheme>"));
+        assert!(pls.contains("<phoneme>təˈmeɪtoʊ</phoneme>"));
+        assert!(pls.contains("<grapheme>AWS</grapheme>"));
+        assert!(pls.contains("<alias>Amazon Web Services</alias>"));
+    }
+
+    #[test]
+    fn export_json_round_trips_entries() {
+        let lex = Lexicon::new(
+            "greeting".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: "hi".to_string(),
+                replacement: "hello".to_string(),
+                phonetic: None,
+                match_mode: MatchMode::ExactWord,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        let json = lex.export_json().unwrap();
+        let parsed: Lexicon = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.name, "greeting");
+        assert_eq!(parsed.entries.len(), 1);
+    }
+
+    #[test]
+    fn dictionary_csv_round_trips_through_export_and_import() {
+        let lex = Lexicon::new(
+            "jp-names".to_string(),
+            "ja".to_string(),
+            vec![LexiconEntry {
+                word: "大谷".to_string(),
+                replacement: "大谷".to_string(),
+                phonetic: Some("おおたに".to_string()),
+                match_mode: MatchMode::CaseInsensitive,
+                accent: Some(3),
+                word_type: None,
+                priority: Some(10),
+            }],
+        );
+        let csv = lex.export(DictionaryFormat::Csv).unwrap();
+        assert_eq!(csv, "word,reading,accent,priority\n大谷,おおたに,3,10\n");
+
+        let imported = Lexicon::import(
+            "jp-names".to_string(),
+            "ja".to_string(),
+            DictionaryFormat::Csv,
+            &csv,
+        )
+        .unwrap();
+        assert_eq!(imported.entries.len(), 1);
+        assert_eq!(imported.entries[0].word, "大谷");
+        assert_eq!(imported.entries[0].accent, Some(3));
+        assert_eq!(imported.entries[0].priority, Some(10));
+    }
+
+    #[test]
+    fn dictionary_csv_quotes_fields_containing_commas_or_quotes() {
+        let lex = Lexicon::new(
+            "en-names".to_string(),
+            "en-US".to_string(),
+            vec![LexiconEntry {
+                word: "Smith, John \"Jack\"".to_string(),
+                replacement: "Smith, John \"Jack\"".to_string(),
+                phonetic: Some("smɪθ dʒɑn".to_string()),
+                match_mode: MatchMode::CaseInsensitive,
+                accent: Some(0),
+                word_type: None,
+                priority: Some(0),
+            }],
+        );
+        let csv = lex.export(DictionaryFormat::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "word,reading,accent,priority\n\"Smith, John \"\"Jack\"\"\",smɪθ dʒɑn,0,0\n"
+        );
+
+        let imported = Lexicon::import(
+            "en-names".to_string(),
+            "en-US".to_string(),
+            DictionaryFormat::Csv,
+            &csv,
+        )
+        .unwrap();
+        assert_eq!(imported.entries.len(), 1);
+        assert_eq!(imported.entries[0].word, "Smith, John \"Jack\"");
+        assert_eq!(imported.entries[0].phonetic.as_deref(), Some("smɪθ dʒɑn"));
+    }
+
+    #[test]
+    fn dictionary_json_round_trips_through_export_and_import() {
+        let lex = Lexicon::new(
+            "greeting".to_string(),
+            "en".to_string(),
+            vec![LexiconEntry {
+                word: "hi".to_string(),
+                replacement: "hi".to_string(),
+                phonetic: Some("haɪ".to_string()),
+                match_mode: MatchMode::ExactWord,
+                accent: None,
+                word_type: None,
+                priority: None,
+            }],
+        );
+        let json = lex.export(DictionaryFormat::Json).unwrap();
+        let imported = Lexicon::import(
+            "greeting".to_string(),
+            "en".to_string(),
+            DictionaryFormat::Json,
+            &json,
+        )
+        .unwrap();
+        assert_eq!(imported.entries.len(), 1);
+        assert_eq!(imported.entries[0].phonetic.as_deref(), Some("haɪ"));
+    }
+
+    #[test]
+    fn import_rejects_accent_beyond_mora_count() {
+        let csv = "word,reading,accent,priority\n大谷,おおたに,9,0\n";
+        let err = Lexicon::import(
+            "jp-names".to_string(),
+            "ja".to_string(),
+            DictionaryFormat::Csv,
+            csv,
+        )
+        .unwrap_err();
+        match err {
+            WitTtsError::InvalidInput(msg) => assert!(msg.contains("exceeds its reading's mora count")),
+            other => panic!("expected InvalidInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn import_rejects_illegal_reading_characters_for_the_language() {
+        let csv = "word,reading,accent,priority\ncat,ねこ123,0,0\n";
+        let err = Lexicon::import(
+            "jp-names".to_string(),
+            "ja".to_string(),
+            DictionaryFormat::Csv,
+            csv,
+        )
+        .unwrap_err();
+        match err {
+            WitTtsError::InvalidInput(msg) => assert!(msg.contains("not a legal symbol")),
+            other => panic!("expected InvalidInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn import_collects_every_invalid_row_before_failing() {
+        let csv = "word,reading,accent,priority\na,ねこ1,0,0\nb,いぬ,9,0\n";
+        let err = Lexicon::import(
+            "jp-names".to_string(),
+            "ja".to_string(),
+            DictionaryFormat::Csv,
+            csv,
+        )
+        .unwrap_err();
+        match err {
+            WitTtsError::InvalidInput(msg) => {
+                assert!(msg.contains("row 1"));
+                assert!(msg.contains("row 2"));
+            }
+            other => panic!("expected InvalidInput, got {other:?}"),
+        }
+    }
+}
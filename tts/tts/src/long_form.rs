@@ -0,0 +1,386 @@
+//! Shared long-form synthesis engine: splits `content` into sub-`max_chars`
+//! segments on sentence/paragraph boundaries (honoring `chapter_breaks` as
+//! hard split points), hands each segment to a provider-supplied closure for
+//! synthesis, and concatenates the results in order.
+//!
+//! Each segment synthesis call is persisted as its own durable operation
+//! (mirroring `synthesize`/`synthesize_batch` in [`crate::durability`]), so
+//! if the component crashes mid-job, replay returns the already-synthesized
+//! segments instead of re-issuing those requests, and only the remaining
+//! segments actually hit the network.
+use crate::exports::golem::tts::advanced::{
+    LongFormJob as WitLongFormJob, LongFormResult as WitLongFormResult,
+};
+use crate::exports::golem::tts::types::TtsError as WitTtsError;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+struct LongFormJobRecord {
+    status: String,
+    total_segments: u32,
+    completed_segments: u32,
+    output_location: String,
+    audio_data: Vec<u8>,
+    error: Option<String>,
+    cancelled: bool,
+}
+
+/// Registry of in-progress and completed long-form jobs, keyed by job id.
+/// Each provider component owns one instance behind its own `thread_local`.
+pub struct LongFormTracker {
+    jobs: RefCell<HashMap<String, LongFormJobRecord>>,
+}
+
+impl Default for LongFormTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LongFormTracker {
+    pub fn new() -> Self {
+        Self {
+            jobs: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Start a long-form job: split `content` into segments no larger than
+    /// `max_chars`, then synthesize each in order via `synthesize_segment`.
+    pub fn start<F>(
+        &self,
+        content: &str,
+        output_location: String,
+        chapter_breaks: Option<Vec<u32>>,
+        max_chars: usize,
+        mut synthesize_segment: F,
+    ) -> Result<WitLongFormJob, WitTtsError>
+    where
+        F: FnMut(&str) -> Result<Vec<u8>, WitTtsError>,
+    {
+        let job_id = uuid::Uuid::new_v4().to_string();
+        let segments = split_into_segments(content, chapter_breaks.as_deref(), max_chars);
+        let total_segments = segments.len() as u32;
+
+        self.jobs.borrow_mut().insert(
+            job_id.clone(),
+            LongFormJobRecord {
+                status: "processing".to_string(),
+                total_segments,
+                completed_segments: 0,
+                output_location,
+                audio_data: Vec::new(),
+                error: None,
+                cancelled: false,
+            },
+        );
+
+        for (index, segment) in segments.iter().enumerate() {
+            if self
+                .jobs
+                .borrow()
+                .get(&job_id)
+                .map(|job| job.cancelled)
+                .unwrap_or(true)
+            {
+                break;
+            }
+
+            match synthesize_segment_durable(&job_id, index as u32, segment, &mut synthesize_segment)
+            {
+                Ok(audio) => {
+                    let mut jobs = self.jobs.borrow_mut();
+                    let job = jobs.get_mut(&job_id).unwrap();
+                    job.audio_data.extend_from_slice(&audio);
+                    job.completed_segments += 1;
+                }
+                Err(e) => {
+                    let mut jobs = self.jobs.borrow_mut();
+                    let job = jobs.get_mut(&job_id).unwrap();
+                    job.status = "failed".to_string();
+                    job.error = Some(format!("{:?}", e));
+                    break;
+                }
+            }
+        }
+
+        let status = {
+            let mut jobs = self.jobs.borrow_mut();
+            let job = jobs.get_mut(&job_id).unwrap();
+            if job.status == "processing" {
+                job.status = if job.completed_segments == job.total_segments {
+                    "completed".to_string()
+                } else {
+                    "cancelled".to_string()
+                };
+            }
+            job.status.clone()
+        };
+
+        Ok(WitLongFormJob {
+            job_id,
+            status,
+            total_segments,
+        })
+    }
+
+    pub fn get_long_form_status(&self, job_id: &str) -> Result<WitLongFormResult, WitTtsError> {
+        let jobs = self.jobs.borrow();
+        let job = jobs
+            .get(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+
+        let percent_complete = if job.total_segments == 0 {
+            100.0
+        } else {
+            (job.completed_segments as f32 / job.total_segments as f32) * 100.0
+        };
+
+        Ok(WitLongFormResult {
+            job_id: job_id.to_string(),
+            status: job.status.clone(),
+            percent_complete,
+            segments_completed: job.completed_segments,
+            total_segments: job.total_segments,
+            output_location: job.output_location.clone(),
+            audio_data: job.audio_data.clone(),
+            error: job.error.clone(),
+        })
+    }
+
+    pub fn cancel_long_form(&self, job_id: &str) -> Result<(), WitTtsError> {
+        let mut jobs = self.jobs.borrow_mut();
+        let job = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| WitTtsError::NotFound(format!("Long-form job {} not found", job_id)))?;
+        job.cancelled = true;
+        if job.status == "processing" {
+            job.status = "cancelled".to_string();
+        }
+        Ok(())
+    }
+}
+
+/// Split `content` on `chapter_breaks` (character offsets treated as hard
+/// split points), then further split each resulting chapter on paragraph and
+/// sentence boundaries so no segment exceeds `max_chars`.
+pub fn split_into_segments(
+    content: &str,
+    chapter_breaks: Option<&[u32]>,
+    max_chars: usize,
+) -> Vec<String> {
+    let chars: Vec<char> = content.chars().collect();
+
+    let mut offsets: Vec<usize> = match chapter_breaks {
+        Some(breaks) if !breaks.is_empty() => {
+            breaks.iter().map(|b| (*b as usize).min(chars.len())).collect()
+        }
+        _ => vec![],
+    };
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let mut segments = Vec::new();
+    let mut start = 0usize;
+    for offset in offsets {
+        if offset > start {
+            let chapter: String = chars[start..offset].iter().collect();
+            segments.extend(split_chapter_into_segments(&chapter, max_chars));
+        }
+        start = offset;
+    }
+    if start < chars.len() {
+        let chapter: String = chars[start..].iter().collect();
+        segments.extend(split_chapter_into_segments(&chapter, max_chars));
+    }
+
+    segments
+}
+
+/// Split a single chapter into segments no larger than `max_chars`,
+/// preferring paragraph breaks, then sentence breaks, then whitespace, so a
+/// segment never splits a sentence unless it has no choice.
+fn split_chapter_into_segments(chapter: &str, max_chars: usize) -> Vec<String> {
+    let trimmed = chapter.trim();
+    if trimmed.is_empty() {
+        return vec![];
+    }
+    if trimmed.chars().count() <= max_chars {
+        return vec![trimmed.to_string()];
+    }
+
+    let paragraphs: Vec<&str> = trimmed.split("\n\n").map(str::trim).filter(|p| !p.is_empty()).collect();
+    let mut segments = Vec::new();
+    let mut current = String::new();
+
+    for paragraph in paragraphs {
+        for sentence in split_into_sentences(paragraph) {
+            if current.chars().count() + sentence.chars().count() + 1 > max_chars && !current.is_empty() {
+                segments.push(std::mem::take(&mut current));
+            }
+
+            if sentence.chars().count() > max_chars {
+                if !current.is_empty() {
+                    segments.push(std::mem::take(&mut current));
+                }
+                segments.extend(hard_split(&sentence, max_chars));
+                continue;
+            }
+
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(&sentence);
+        }
+    }
+
+    if !current.is_empty() {
+        segments.push(current);
+    }
+
+    segments
+}
+
+/// Split `text` on sentence-ending punctuation (`.`, `!`, `?`) followed by
+/// whitespace, keeping the punctuation with its sentence.
+fn split_into_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        current.push(ch);
+        if matches!(ch, '.' | '!' | '?') && chars.peek().map_or(true, |c| c.is_whitespace()) {
+            sentences.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+
+    sentences.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+/// Last-resort split for a single sentence longer than `max_chars`: break on
+/// whitespace so words are never split across a boundary.
+fn hard_split(text: &str, max_chars: usize) -> Vec<String> {
+    let words = text.split_whitespace();
+    let mut fragments = Vec::new();
+    let mut current = String::new();
+
+    for word in words {
+        if current.chars().count() + word.chars().count() + 1 > max_chars && !current.is_empty() {
+            fragments.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        fragments.push(current);
+    }
+
+    fragments
+}
+
+#[cfg(feature = "durability")]
+mod durable_segment {
+    use super::WitTtsError;
+    use golem_rust::bindings::golem::durability::durability::DurableFunctionType;
+    use golem_rust::durability::Durability;
+    use golem_rust::{with_persistence_level, FromValueAndType, IntoValue, PersistenceLevel};
+
+    #[derive(Debug, Clone, PartialEq, IntoValue, FromValueAndType)]
+    struct LongFormSegmentInput {
+        job_id: String,
+        index: u32,
+        text: String,
+    }
+
+    pub(super) fn synthesize_segment_durable<F>(
+        job_id: &str,
+        index: u32,
+        text: &str,
+        synthesize_segment: &mut F,
+    ) -> Result<Vec<u8>, WitTtsError>
+    where
+        F: FnMut(&str) -> Result<Vec<u8>, WitTtsError>,
+    {
+        let durability = Durability::<Vec<u8>, WitTtsError>::new(
+            "golem_tts",
+            "synthesize_long_form_segment",
+            DurableFunctionType::WriteRemote,
+        );
+
+        if durability.is_live() {
+            let result = with_persistence_level(PersistenceLevel::PersistNothing, || {
+                synthesize_segment(text)
+            });
+            durability.persist(
+                LongFormSegmentInput {
+                    job_id: job_id.to_string(),
+                    index,
+                    text: text.to_string(),
+                },
+                result,
+            )
+        } else {
+            durability.replay()
+        }
+    }
+}
+
+#[cfg(feature = "durability")]
+use durable_segment::synthesize_segment_durable;
+
+#[cfg(not(feature = "durability"))]
+fn synthesize_segment_durable<F>(
+    _job_id: &str,
+    _index: u32,
+    text: &str,
+    synthesize_segment: &mut F,
+) -> Result<Vec<u8>, WitTtsError>
+where
+    F: FnMut(&str) -> Result<Vec<u8>, WitTtsError>,
+{
+    synthesize_segment(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_chapter_is_a_single_segment() {
+        let segments = split_into_segments("Hello world.", None, 5000);
+        assert_eq!(segments, vec!["Hello world.".to_string()]);
+    }
+
+    #[test]
+    fn long_chapter_splits_on_sentence_boundaries() {
+        let text = format!("{} {}", "a".repeat(30) + ".", "b".repeat(30) + ".");
+        let segments = split_into_segments(&text, None, 35);
+        assert_eq!(segments.len(), 2);
+        assert!(segments[0].ends_with('.'));
+        assert!(segments[1].ends_with('.'));
+    }
+
+    #[test]
+    fn chapter_breaks_are_hard_split_points() {
+        let text = "First chapter. Second chapter.";
+        let segments = split_into_segments(text, Some(&[15]), 5000);
+        assert_eq!(
+            segments,
+            vec!["First chapter.".to_string(), "Second chapter.".to_string()]
+        );
+    }
+
+    #[test]
+    fn oversized_sentence_falls_back_to_whitespace_split() {
+        let sentence = format!("{}.", "word ".repeat(20).trim());
+        let segments = split_into_segments(&sentence, None, 20);
+        assert!(segments.len() > 1);
+        assert!(segments.iter().all(|s| s.chars().count() <= 20 || !s.contains(' ')));
+    }
+}
@@ -8,6 +8,10 @@ pub struct RetryConfig {
     pub initial_delay_ms: u64,
     pub max_delay_ms: u64,
     pub backoff_multiplier: f64,
+    /// Apply full-jitter (sleep a random duration in `[0, capped_delay]`)
+    /// so concurrent retriers desynchronize instead of waking in lockstep
+    /// and re-colliding on the same rate limit.
+    pub jitter: bool,
 }
 
 impl Default for RetryConfig {
@@ -17,6 +21,7 @@ impl Default for RetryConfig {
             initial_delay_ms: 1000,
             max_delay_ms: 30000,
             backoff_multiplier: 2.0,
+            jitter: false,
         }
     }
 }
@@ -32,11 +37,44 @@ impl RetryConfig {
         self
     }
 
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Capped exponential delay for `attempt`, before any Retry-After
+    /// override or jitter is applied.
     pub fn calculate_delay(&self, attempt: u32) -> Duration {
         let delay = self.initial_delay_ms as f64 * self.backoff_multiplier.powi(attempt as i32);
         let capped = delay.min(self.max_delay_ms as f64);
         Duration::from_millis(capped as u64)
     }
+
+    /// The delay to actually sleep before retrying after `error`: a
+    /// `RateLimited(secs)` error overrides the computed backoff with the
+    /// server-provided wait; if `jitter` is enabled, the sleep is then drawn
+    /// uniformly from `[0, delay]`, server-provided waits included.
+    fn delay_for(&self, attempt: u32, error: &TtsError) -> Duration {
+        let delay = match error {
+            TtsError::RateLimited(secs) => Duration::from_secs(*secs as u64),
+            _ => self.calculate_delay(attempt),
+        };
+        apply_jitter(delay, self.jitter)
+    }
+}
+
+/// Full-jitter a delay down to a uniform random value in `[0, delay]`,
+/// using the given RNG so callers (tests included) can make it
+/// deterministic.
+fn apply_jitter_with_rng<R: rand::Rng>(delay: Duration, jitter: bool, rng: &mut R) -> Duration {
+    if !jitter || delay.is_zero() {
+        return delay;
+    }
+    Duration::from_millis(rng.gen_range(0..=delay.as_millis() as u64))
+}
+
+fn apply_jitter(delay: Duration, jitter: bool) -> Duration {
+    apply_jitter_with_rng(delay, jitter, &mut rand::thread_rng())
 }
 
 /// Retry an operation with exponential backoff
@@ -55,8 +93,8 @@ where
                     return Err(e);
                 }
 
+                let delay = config.delay_for(attempt, &e);
                 last_error = Some(e);
-                let delay = config.calculate_delay(attempt);
                 std::thread::sleep(delay);
             }
         }
@@ -87,6 +125,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::SeedableRng;
 
     #[test]
     fn test_retry_config_delay_calculation() {
@@ -111,4 +150,29 @@ mod tests {
         assert!(!is_retryable(&TtsError::InvalidText("test".to_string())));
         assert!(!is_retryable(&TtsError::VoiceNotFound("test".to_string())));
     }
+
+    #[test]
+    fn test_rate_limited_overrides_computed_backoff() {
+        let config = RetryConfig::default();
+        let delay = config.delay_for(0, &TtsError::RateLimited(42));
+        assert_eq!(delay, Duration::from_secs(42));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let capped = Duration::from_millis(4000);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let jittered = apply_jitter_with_rng(capped, true, &mut rng);
+            assert!(jittered <= capped);
+        }
+    }
+
+    #[test]
+    fn test_jitter_disabled_is_exact() {
+        let capped = Duration::from_millis(4000);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        assert_eq!(apply_jitter_with_rng(capped, false, &mut rng), capped);
+    }
 }
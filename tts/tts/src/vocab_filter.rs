@@ -0,0 +1,219 @@
+//! Client-side text vocabulary filter: sanitize a [`TextInput`] before it
+//! reaches `synthesize`, `synthesize_batch`, or `stream_send_text`, the way
+//! [`crate::lexicon`] rewrites pronunciation rather than content. Borrows
+//! the mask/remove/tag vocabulary-filter-method vocabulary from streaming
+//! transcribers: `mask` bleeps each match, `remove` deletes it (and its
+//! one surrounding space), and `tag` leaves the text untouched and only
+//! reports where matches were found so a caller can decide for itself.
+//! Useful for profanity redaction and compliance pipelines.
+
+use crate::golem::tts::types::TextInput;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How `apply_vocabulary_filter` transforms a matched phrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilterMode {
+    /// Replace the match with the filter's `replacement` placeholder.
+    Mask,
+    /// Delete the match along with one surrounding space, if present.
+    Remove,
+    /// Leave the text unchanged; only report matches.
+    Tag,
+}
+
+/// One match of a filter phrase, as a byte-offset span into the
+/// *original*, pre-transform text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    pub phrase: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A named, language-scoped vocabulary to match against text, identified
+/// the same way a [`crate::lexicon::Lexicon`] is (a UUID, so the two share
+/// one id space).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyFilter {
+    pub id: String,
+    pub language: String,
+    pub phrases: Vec<String>,
+    pub replacement: String,
+}
+
+impl VocabularyFilter {
+    pub fn new(language: String, phrases: Vec<String>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            language,
+            phrases,
+            replacement: "[bleep]".to_string(),
+        }
+    }
+
+    /// Override the placeholder `mask` mode substitutes in for a match.
+    pub fn with_replacement(mut self, replacement: String) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Find every case-insensitive, word-boundary match of this filter's
+    /// phrases in `text`. Phrases are tried longest-first so a multi-word
+    /// phrase wins over a shorter one it contains, and a span already
+    /// claimed by an earlier (longer) phrase can't be matched again.
+    fn find_matches(&self, text: &str) -> Vec<MatchSpan> {
+        let mut phrases: Vec<&String> = self.phrases.iter().collect();
+        phrases.sort_by_key(|p| std::cmp::Reverse(p.len()));
+
+        let mut covered = vec![false; text.len()];
+        let mut spans = Vec::new();
+        for phrase in phrases {
+            if phrase.is_empty() {
+                continue;
+            }
+            let pattern = format!(r"(?i)\b{}\b", regex::escape(phrase));
+            let Ok(re) = Regex::new(&pattern) else {
+                continue;
+            };
+            for m in re.find_iter(text) {
+                if covered[m.start()..m.end()].iter().any(|taken| *taken) {
+                    continue;
+                }
+                covered[m.start()..m.end()].iter_mut().for_each(|taken| *taken = true);
+                spans.push(MatchSpan {
+                    phrase: phrase.clone(),
+                    start: m.start() as u32,
+                    end: m.end() as u32,
+                });
+            }
+        }
+        spans.sort_by_key(|span| span.start);
+        spans
+    }
+
+    /// Apply `mode` to `text`, returning the transformed text alongside
+    /// every matched span (offsets into the original `text`, regardless of
+    /// `mode`).
+    pub fn apply(&self, text: &str, mode: FilterMode) -> (String, Vec<MatchSpan>) {
+        let spans = self.find_matches(text);
+        let transformed = match mode {
+            FilterMode::Tag => text.to_string(),
+            FilterMode::Mask => {
+                let mut out = String::with_capacity(text.len());
+                let mut cursor = 0usize;
+                for span in &spans {
+                    out.push_str(&text[cursor..span.start as usize]);
+                    out.push_str(&self.replacement);
+                    cursor = span.end as usize;
+                }
+                out.push_str(&text[cursor..]);
+                out
+            }
+            FilterMode::Remove => {
+                let mut out = String::with_capacity(text.len());
+                let mut cursor = 0usize;
+                for span in &spans {
+                    let mut start = span.start as usize;
+                    let mut removed_space = false;
+                    if start > cursor && text.as_bytes().get(start - 1) == Some(&b' ') {
+                        start -= 1;
+                        removed_space = true;
+                    }
+                    out.push_str(&text[cursor..start]);
+                    cursor = span.end as usize;
+                    if !removed_space && text.as_bytes().get(cursor) == Some(&b' ') {
+                        cursor += 1;
+                    }
+                }
+                out.push_str(&text[cursor..]);
+                out
+            }
+        };
+        (transformed, spans)
+    }
+}
+
+/// Run `filter` over `input.content` in `mode`, returning the (possibly
+/// rewritten) [`TextInput`] and every matched span.
+pub fn apply_vocabulary_filter(
+    input: TextInput,
+    filter: &VocabularyFilter,
+    mode: FilterMode,
+) -> (TextInput, Vec<MatchSpan>) {
+    let (content, spans) = filter.apply(&input.content, mode);
+    let mut output = input;
+    output.content = content;
+    (output, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(phrases: &[&str]) -> VocabularyFilter {
+        VocabularyFilter::new(
+            "en".to_string(),
+            phrases.iter().map(|p| p.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn mask_replaces_matches_with_the_placeholder() {
+        let f = filter(&["darn"]);
+        let (text, spans) = f.apply("Well darn it all", FilterMode::Mask);
+        assert_eq!(text, "Well [bleep] it all");
+        assert_eq!(spans, vec![MatchSpan { phrase: "darn".to_string(), start: 5, end: 9 }]);
+    }
+
+    #[test]
+    fn remove_deletes_the_match_and_one_surrounding_space() {
+        let f = filter(&["darn"]);
+        let (text, _) = f.apply("Well darn it", FilterMode::Remove);
+        assert_eq!(text, "Well it");
+    }
+
+    #[test]
+    fn tag_leaves_text_untouched_but_reports_spans() {
+        let f = filter(&["darn"]);
+        let (text, spans) = f.apply("Well darn it", FilterMode::Tag);
+        assert_eq!(text, "Well darn it");
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn matching_is_case_insensitive_with_word_boundaries() {
+        let f = filter(&["cat"]);
+        let (text, spans) = f.apply("The CAT sat, category unaffected", FilterMode::Mask);
+        assert_eq!(text, "The [bleep] sat, category unaffected");
+        assert_eq!(spans.len(), 1);
+    }
+
+    #[test]
+    fn multi_word_phrases_win_over_contained_shorter_phrases() {
+        let f = filter(&["bad word", "word"]);
+        let (text, spans) = f.apply("That's a bad word to use", FilterMode::Mask);
+        assert_eq!(text, "That's a [bleep] to use");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].phrase, "bad word");
+    }
+
+    #[test]
+    fn custom_replacement_is_honored() {
+        let f = filter(&["darn"]).with_replacement("***".to_string());
+        let (text, _) = f.apply("darn it", FilterMode::Mask);
+        assert_eq!(text, "*** it");
+    }
+
+    #[test]
+    fn apply_vocabulary_filter_rewrites_text_input_content() {
+        let f = filter(&["darn"]);
+        let input = TextInput {
+            content: "darn it".to_string(),
+            text_type: crate::golem::tts::types::TextType::Plain,
+        };
+        let (output, spans) = apply_vocabulary_filter(input, &f, FilterMode::Mask);
+        assert_eq!(output.content, "[bleep] it");
+        assert_eq!(spans.len(), 1);
+    }
+}
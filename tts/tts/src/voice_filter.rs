@@ -0,0 +1,75 @@
+//! Shared `VoiceFilter` matching so each provider's `list_voices`/
+//! `search_voices` apply the same rules instead of reimplementing ad-hoc
+//! field checks.
+use crate::exports::golem::tts::voices::{VoiceFilter, VoiceInfo};
+use crate::lang::LanguageIdentifier;
+
+/// Whether `voice` satisfies every field set on `filter`. Unset fields are
+/// ignored (treated as a match).
+pub fn matches_filter(voice: &VoiceInfo, filter: &VoiceFilter) -> bool {
+    if let Some(gender) = filter.gender {
+        if voice.gender != gender {
+            return false;
+        }
+    }
+
+    if let Some(ref language) = filter.language {
+        let requested = LanguageIdentifier::parse(language);
+        let own = LanguageIdentifier::parse(&voice.language);
+        let matches_primary = own.matches(&requested);
+        let matches_additional = voice
+            .additional_languages
+            .iter()
+            .any(|l| LanguageIdentifier::parse(l).matches(&requested));
+        if !matches_primary && !matches_additional {
+            return false;
+        }
+    }
+
+    if let Some(quality) = filter.quality {
+        if voice.quality != quality {
+            return false;
+        }
+    }
+
+    if let Some(ref use_case) = filter.use_case {
+        let use_case_lower = use_case.to_lowercase();
+        if !voice
+            .use_cases
+            .iter()
+            .any(|u| u.to_lowercase().contains(&use_case_lower))
+        {
+            return false;
+        }
+    }
+
+    if let Some(is_cloned) = filter.is_cloned {
+        if voice.is_cloned != is_cloned {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Filter and text-search a voice catalog in one pass, matching the
+/// `search_voices` + `VoiceFilter` combination every provider exposes.
+pub fn search_and_filter<'a>(
+    voices: impl IntoIterator<Item = &'a VoiceInfo>,
+    query: Option<&str>,
+    filter: Option<&VoiceFilter>,
+) -> Vec<VoiceInfo> {
+    let query_lower = query.map(|q| q.to_lowercase());
+
+    voices
+        .into_iter()
+        .filter(|v| {
+            query_lower
+                .as_ref()
+                .map(|q| v.name.to_lowercase().contains(q) || v.id.to_lowercase().contains(q))
+                .unwrap_or(true)
+        })
+        .filter(|v| filter.map(|f| matches_filter(v, f)).unwrap_or(true))
+        .cloned()
+        .collect()
+}